@@ -33,6 +33,7 @@ fn my_adding_func_b(index_a: usize, index_b: usize) -> Vec<u32> {
     v.unfix()
 }
 
+#[allow(clippy::needless_range_loop)]
 fn comparison_benchmark(c: &mut Criterion) {
     c.bench_function("inc 2 indices 10000 times (no fixed_vec)", |b| {
         b.iter(|| {
@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use type_name_value::Named;
+
+use crate::{Brand, CheckedRange, FixedVec, Index};
+
+/// An append-only arena whose ids are ``Index``'s. Since a ``FixedArena`` never shrinks, looking
+/// up an id never fails and never needs a bounds check.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let values = Brand::with(|brand| {
+///     let mut arena = FixedArena::new(brand);
+///     let a = arena.alloc("a");
+///     let rest = arena.alloc_extend(vec!["b", "c"]);
+///
+///     assert_eq!(arena[a], "a");
+///     assert_eq!(rest.map(|id| arena[id]).collect::<Vec<_>>(), vec!["b", "c"]);
+///     arena.into_vec()
+/// });
+///
+/// assert_eq!(values, vec!["a", "b", "c"]);
+/// ```
+///
+/// Every arena needs its own token, so ids of one arena can't be used with another:
+///
+/// ```compile_fail
+/// # use fixed_vec::*;
+/// Brand::with(|brand| {
+///     let mut a = FixedArena::new(brand);
+///     let mut b = FixedArena::new(brand);
+///     let id = a.alloc(1);
+///     b.alloc(2);
+///     println!("{}", b[id]);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct FixedArena<A, Name> {
+    inner: FixedVec<A, Name>,
+}
+
+impl<A, Name> Deref for FixedArena<A, Name> {
+    type Target = [A];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'id, A> FixedArena<A, Brand<'id>> {
+    /// Create an empty ``FixedArena``, named by a ``Brand`` token from ``Brand::with``.
+    pub fn new(_brand: Brand<'id>) -> Self {
+        FixedArena {
+            inner: unsafe {
+                // Brand<'id> is unique, since its only token is used up here
+                FixedVec::empty()
+            },
+        }
+    }
+}
+
+impl<A, Name> FixedArena<A, Name> {
+    /// Create a ``FixedArena`` from a named ``Vec``, keeping its elements in order.
+    pub fn fix(val: Named<Vec<A>, Name>) -> Self {
+        FixedArena {
+            inner: FixedVec::fix(val),
+        }
+    }
+
+    /// Unwrap's the allocated elements, in allocation order. Since this takes ownership of the
+    /// ``FixedArena``, it indirectly invalidates all ids with the same ``Name``.
+    pub fn into_vec(self) -> Vec<A> {
+        self.inner.unfix()
+    }
+
+    /// Allocate a new element, returning its id.
+    pub fn alloc(&mut self, value: A) -> Index<Name> {
+        let index = self.inner.len();
+        self.inner.push(value);
+        Index {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Allocate many elements at once, returning the range of their ids.
+    pub fn alloc_extend<I: IntoIterator<Item = A>>(&mut self, values: I) -> CheckedRange<Name> {
+        let start = self.inner.len();
+        for value in values {
+            self.inner.push(value);
+        }
+        CheckedRange {
+            range: start..self.inner.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Check that ``index`` is the id of an allocated element.
+    pub fn check_index(&self, index: usize) -> Option<Index<Name>> {
+        self.inner.check_index(index)
+    }
+
+    /// The ids of every allocated element, in allocation order.
+    pub fn ids(&self) -> CheckedRange<Name> {
+        CheckedRange {
+            range: 0..self.inner.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over every allocated element along with its id, in allocation order.
+    pub fn iter_indexed(&self) -> impl DoubleEndedIterator<Item = (Index<Name>, &A)> + ExactSizeIterator {
+        self.ids().zip(self.inner.iter())
+    }
+
+    /// Get an element without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, id: Index<Name>) -> &A {
+        self.inner.get(id)
+    }
+
+    /// Get a mutable reference to an element without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, id: Index<Name>) -> &mut A {
+        self.inner.get_mut(id)
+    }
+}
+
+impl<A, Name> std::ops::Index<Index<Name>> for FixedArena<A, Name> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, id: Index<Name>) -> &A {
+        self.get(id)
+    }
+}
+
+impl<A, Name> std::ops::IndexMut<Index<Name>> for FixedArena<A, Name> {
+    #[inline(always)]
+    fn index_mut(&mut self, id: Index<Name>) -> &mut A {
+        self.get_mut(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptionIndex;
+
+    struct Tree<Name> {
+        value: u32,
+        children: Vec<Index<Name>>,
+        parent: OptionIndex<Name>,
+    }
+
+    #[test]
+    fn tree_in_arena() {
+        Brand::with(|brand| {
+            let mut arena = FixedArena::new(brand);
+            let root = arena.alloc(Tree { value: 1, children: vec![], parent: OptionIndex::none() });
+            for value in 2..5 {
+                let child = arena.alloc(Tree { value, children: vec![], parent: root.into() });
+                arena[root].children.push(child);
+            }
+
+            let total: u32 = arena[root].children.iter().map(|&child| arena[child].value).sum();
+            assert_eq!(total, 9);
+            assert!(arena.iter_indexed().skip(1).all(|(_, node)| node.parent.get() == Some(root)));
+            assert_eq!(arena.ids().len(), 4);
+            assert_eq!(arena.iter().map(|node| node.value).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        });
+    }
+}
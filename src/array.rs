@@ -0,0 +1,318 @@
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::{FixedVec, Index};
+
+/// An index that is known to be less than ``N``. Unlike ``Index``, an ``Idx<N>`` doesn't need a
+/// name, since it is valid in every ``[A; N]``, ``Box<[A; N]>`` and ``FixedArray<A, N>``.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let a = [1, 2, 3];
+/// let b = Box::new(['a', 'b', 'c']);
+///
+/// let index = Idx::<3>::new(2).unwrap();
+///
+/// assert_eq!(index.get(&a), &3);
+/// assert_eq!(index.get(&b), &'c');
+/// assert!(Idx::<3>::new(3).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Idx<const N: usize> {
+    index: usize,
+}
+
+impl<const N: usize> Deref for Idx<N> {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+impl<const N: usize> Idx<N> {
+    /// Perform an index bounds check against ``N``. This is the only way to directly create an
+    /// ``Idx``.
+    pub fn new(index: usize) -> Option<Self> {
+        if index < N {
+            Some(Idx {
+                index,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every ``Idx<N>`` in order.
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator {
+        (0..N).map(|index| Idx {
+            index,
+        })
+    }
+
+    /// Add ``n`` to this index, wrapping around to 0 at ``N``.
+    #[inline(always)]
+    pub fn wrapping_add(self, n: usize) -> Self {
+        let n = n % N;
+        let index = if n >= N - self.index {
+            n - (N - self.index)
+        } else {
+            self.index + n
+        };
+        Idx {
+            index,
+        }
+    }
+
+    /// Subtract ``n`` from this index, wrapping around to ``N - 1`` below 0.
+    #[inline(always)]
+    pub fn wrapping_sub(self, n: usize) -> Self {
+        let n = n % N;
+        let index = if n <= self.index {
+            self.index - n
+        } else {
+            self.index + (N - n)
+        };
+        Idx {
+            index,
+        }
+    }
+
+    /// The next index, wrapping around to 0 after ``N - 1``.
+    #[inline(always)]
+    pub fn next(self) -> Self {
+        self.wrapping_add(1)
+    }
+
+    /// The previous index, wrapping around to ``N - 1`` before 0.
+    #[inline(always)]
+    pub fn prev(self) -> Self {
+        self.wrapping_sub(1)
+    }
+
+    /// Get an element of an array without bounds checking.
+    #[inline(always)]
+    pub fn get<A>(self, array: &[A; N]) -> &A {
+        unsafe {
+            array.get_unchecked(self.index)
+        }
+    }
+
+    /// Get a mutable reference to an element of an array without bounds checking.
+    #[inline(always)]
+    pub fn get_mut<A>(self, array: &mut [A; N]) -> &mut A {
+        unsafe {
+            array.get_unchecked_mut(self.index)
+        }
+    }
+}
+
+/// An integer in the range ``0..N``, with wrapping arithmetic for use as a position in a
+/// ``FixedRing``. This is the same type as ``Idx<N>``.
+pub type Bounded<const N: usize> = Idx<N>;
+
+/// A wrapper around an array of length ``N`` that can be indexed by an ``Idx<N>`` without bounds
+/// checking.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let mut a = FixedArray::new([0u32; 8]);
+///
+/// for index in Idx::all() {
+///     a[index] += *index as u32;
+/// }
+///
+/// assert_eq!(a.into_inner(), [0, 1, 2, 3, 4, 5, 6, 7]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FixedArray<A, const N: usize> {
+    inner: [A; N],
+}
+
+impl<A, const N: usize> FixedArray<A, N> {
+    /// Wrap an array.
+    pub fn new(array: [A; N]) -> Self {
+        FixedArray {
+            inner: array,
+        }
+    }
+
+    /// Unwrap the inner array.
+    pub fn into_inner(self) -> [A; N] {
+        self.inner
+    }
+
+    /// Get an element of the ``FixedArray`` without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Idx<N>) -> &A {
+        index.get(&self.inner)
+    }
+
+    /// Get a mutable reference to an element of the ``FixedArray`` without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: Idx<N>) -> &mut A {
+        index.get_mut(&mut self.inner)
+    }
+}
+
+impl<A, const N: usize> From<[A; N]> for FixedArray<A, N> {
+    fn from(array: [A; N]) -> Self {
+        FixedArray::new(array)
+    }
+}
+
+impl<A, const N: usize> Deref for FixedArray<A, N> {
+    type Target = [A; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<A, const N: usize> DerefMut for FixedArray<A, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<A, const N: usize> std::ops::Index<Idx<N>> for FixedArray<A, N> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: Idx<N>) -> &A {
+        self.get(index)
+    }
+}
+
+impl<A, const N: usize> std::ops::IndexMut<Idx<N>> for FixedArray<A, N> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: Idx<N>) -> &mut A {
+        self.get_mut(index)
+    }
+}
+
+/// A proof that the ``FixedVec`` with name ``Name`` has length exactly ``N``, so that ``Index``'s
+/// and ``Idx``'s can be converted into each other. The proof borrows the ``FixedVec`` for the
+/// lifetime ``'a`` so that it can't grow while the proof is alive.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""))]
+pub struct LenIs<'a, Name, const N: usize> {
+    _phantom: PhantomData<(&'a (), Name)>,
+}
+
+impl<'a, Name, const N: usize> LenIs<'a, Name, N> {
+    /// Convert an ``Index`` into an ``Idx``.
+    pub fn to_idx(&self, index: Index<Name>) -> Idx<N> {
+        Idx {
+            index: index.index,
+        }
+    }
+
+    /// Convert an ``Idx`` into an ``Index``.
+    pub fn to_index(&self, index: Idx<N>) -> Index<Name> {
+        Index {
+            index: index.index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A, Name> FixedVec<A, Name> {
+    /// Check that the ``FixedVec`` has length exactly ``N``.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// let v = vec![1, 2, 3];
+    /// let v = name!(v);
+    /// let v = FixedVec::fix(v);
+    ///
+    /// let len = v.check_len::<3>().unwrap();
+    /// let weights = FixedArray::new([10, 20, 30]);
+    ///
+    /// let total: i32 = Idx::<3>::all().map(|i| v.get(len.to_index(i)) * weights[i]).sum();
+    /// assert_eq!(total, 140);
+    /// ```
+    pub fn check_len<const N: usize>(&self) -> Option<LenIs<'_, Name, N>> {
+        if self.len() == N {
+            Some(LenIs {
+                _phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Convert the ``FixedVec`` into a ``FixedArray``, if it has length exactly ``N``. Otherwise,
+    /// the ``FixedVec`` is given back unchanged.
+    pub fn into_fixed_array<const N: usize>(self) -> Result<FixedArray<A, N>, Self> {
+        if self.len() != N {
+            return Err(self);
+        }
+        match <[A; N]>::try_from(self.inner.unname()) {
+            Ok(array) => Ok(FixedArray::new(array)),
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn idx_in_arrays() {
+        let mut a = FixedArray::from([1, 2, 3, 4]);
+        let mut b = Box::new([0; 4]);
+
+        for index in Idx::<4>::all().rev() {
+            *index.get_mut(&mut b) = a[index] * 2;
+            a[index] += 1;
+        }
+
+        assert_eq!(*b, [2, 4, 6, 8]);
+        assert_eq!(a.into_inner(), [2, 3, 4, 5]);
+        assert_eq!(Idx::<4>::all().len(), 4);
+        assert!(Idx::<0>::new(0).is_none());
+    }
+
+    #[test]
+    fn fixed_vec_conversions() {
+        let v = vec![1, 2, 3];
+        let v = name!(v);
+        let v = FixedVec::fix(v);
+
+        assert!(v.check_len::<2>().is_none());
+
+        let len = v.check_len::<3>().unwrap();
+        let index = v.check_index(1).unwrap();
+        assert_eq!(*len.to_idx(index), 1);
+
+        let v = v.into_fixed_array::<4>().unwrap_err();
+        let a = v.into_fixed_array::<3>().ok().unwrap();
+        assert_eq!(a.into_inner(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        let a = Bounded::<5>::new(3).unwrap();
+        assert_eq!(*a.wrapping_add(1), 4);
+        assert_eq!(*a.wrapping_add(2), 0);
+        assert_eq!(*a.wrapping_add(13), 1);
+        assert_eq!(*a.wrapping_sub(3), 0);
+        assert_eq!(*a.wrapping_sub(4), 4);
+        assert_eq!(*a.wrapping_sub(usize::MAX), 3);
+        assert_eq!(*a.next().next(), 0);
+        assert_eq!(*Bounded::<5>::new(0).unwrap().prev(), 4);
+
+        let b = Bounded::<{ usize::MAX }>::new(usize::MAX - 1).unwrap();
+        assert_eq!(*b.wrapping_add(2), 1);
+    }
+}
@@ -0,0 +1,364 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ops::Range;
+use std::sync::atomic::Ordering;
+
+use type_name_value::Named;
+
+use crate::{CheckedRange, Index};
+
+/// An atomic integer type, like ``AtomicU64``, that can be stored in a ``FixedAtomicVec``. Each
+/// method forwards to the method of the same name on the atomic type.
+pub trait AtomicInteger: Send + Sync {
+    /// The integer type stored inside the atomic, like ``u64`` for ``AtomicU64``.
+    type Value: Copy;
+
+    /// Create a new atomic integer holding ``value``.
+    fn new(value: Self::Value) -> Self;
+
+    /// Unwrap the atomic, returning the value it holds. This doesn't need any synchronization,
+    /// since it takes ownership of the atomic.
+    fn into_inner(self) -> Self::Value;
+
+    /// Load the current value.
+    fn load(&self, order: Ordering) -> Self::Value;
+
+    /// Store ``value``, replacing the current value.
+    fn store(&self, value: Self::Value, order: Ordering);
+
+    /// Store ``value``, returning the previous value.
+    fn swap(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Store ``new`` if the current value is equal to ``current``. Returns the previous value,
+    /// which is wrapped in ``Ok`` if ``new`` was stored and ``Err`` if it wasn't. ``success`` is
+    /// the ordering used if the value was stored, and ``failure`` is the ordering of the load
+    /// otherwise.
+    fn compare_exchange(&self, current: Self::Value, new: Self::Value, success: Ordering, failure: Ordering) -> Result<Self::Value, Self::Value>;
+
+    /// Add ``value`` to the current value, wrapping around on overflow. Returns the previous
+    /// value.
+    fn fetch_add(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Subtract ``value`` from the current value, wrapping around on overflow. Returns the
+    /// previous value.
+    fn fetch_sub(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Bitwise "and" the current value with ``value``. Returns the previous value.
+    fn fetch_and(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Bitwise "or" the current value with ``value``. Returns the previous value.
+    fn fetch_or(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Bitwise "xor" the current value with ``value``. Returns the previous value.
+    fn fetch_xor(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Set the current value to the maximum of itself and ``value``. Returns the previous value.
+    fn fetch_max(&self, value: Self::Value, order: Ordering) -> Self::Value;
+
+    /// Set the current value to the minimum of itself and ``value``. Returns the previous value.
+    fn fetch_min(&self, value: Self::Value, order: Ordering) -> Self::Value;
+}
+
+macro_rules! impl_atomic_integer {
+    ($($width:literal: $($atomic:ident => $value:ty),*;)*) => {
+        $($(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicInteger for std::sync::atomic::$atomic {
+                type Value = $value;
+
+                fn new(value: $value) -> Self {
+                    Self::new(value)
+                }
+
+                fn into_inner(self) -> $value {
+                    self.into_inner()
+                }
+
+                fn load(&self, order: Ordering) -> $value {
+                    self.load(order)
+                }
+
+                fn store(&self, value: $value, order: Ordering) {
+                    self.store(value, order)
+                }
+
+                fn swap(&self, value: $value, order: Ordering) -> $value {
+                    self.swap(value, order)
+                }
+
+                fn compare_exchange(&self, current: $value, new: $value, success: Ordering, failure: Ordering) -> Result<$value, $value> {
+                    self.compare_exchange(current, new, success, failure)
+                }
+
+                fn fetch_add(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_add(value, order)
+                }
+
+                fn fetch_sub(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_sub(value, order)
+                }
+
+                fn fetch_and(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_and(value, order)
+                }
+
+                fn fetch_or(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_or(value, order)
+                }
+
+                fn fetch_xor(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_xor(value, order)
+                }
+
+                fn fetch_max(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_max(value, order)
+                }
+
+                fn fetch_min(&self, value: $value, order: Ordering) -> $value {
+                    self.fetch_min(value, order)
+                }
+            }
+        )*)*
+    };
+}
+
+impl_atomic_integer! {
+    "8": AtomicU8 => u8, AtomicI8 => i8;
+    "16": AtomicU16 => u16, AtomicI16 => i16;
+    "32": AtomicU32 => u32, AtomicI32 => i32;
+    "64": AtomicU64 => u64, AtomicI64 => i64;
+    "ptr": AtomicUsize => usize, AtomicIsize => isize;
+}
+
+/// A ``FixedVec`` of atomic integers, so that elements can be updated from many threads at once
+/// through a shared reference, without bounds checks.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// let buckets: Vec<AtomicU64> = (0..4).map(|_| AtomicU64::new(0)).collect();
+/// let buckets = name!(buckets);
+/// let buckets = FixedAtomicVec::fix(buckets);
+///
+/// let even = buckets.check_index(0).unwrap();
+/// let odd = buckets.check_index(1).unwrap();
+///
+/// std::thread::scope(|scope| {
+///     for t in 0..4 {
+///         let buckets = &buckets;
+///         scope.spawn(move || {
+///             for i in 0..100 {
+///                 let bucket = if (t + i) % 2 == 0 { even } else { odd };
+///                 buckets.fetch_add(bucket, 1, Ordering::Relaxed);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(buckets.into_values(), vec![200, 200, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct FixedAtomicVec<A, Name> {
+    inner: Vec<A>,
+    _phantom: PhantomData<Name>,
+}
+
+impl<A, Name> Deref for FixedAtomicVec<A, Name> {
+    type Target = [A];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<A: AtomicInteger, Name> FixedAtomicVec<A, Name> {
+    /// Create a ``FixedAtomicVec`` from a named ``Vec``. To use this method, first assign a name to
+    /// a ``Vec`` using ``name!()``.
+    pub fn fix(val: Named<Vec<A>, Name>) -> Self {
+        FixedAtomicVec {
+            inner: val.unname(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Unwrap's the inner ``Vec``. Since this takes ownership of the ``FixedAtomicVec``, it
+    /// indirectly invalidates all ``Index``'s with the same ``Name``.
+    pub fn unfix(self) -> Vec<A> {
+        self.inner
+    }
+
+    /// Unwrap's the current values of all the atomics into a ``Vec``.
+    pub fn into_values(self) -> Vec<A::Value> {
+        self.inner.into_iter().map(A::into_inner).collect()
+    }
+
+    /// Perform an index bounds check. This is the only way to directly create an ``Index`` for a
+    /// ``FixedAtomicVec``.
+    pub fn check_index(&self, index: usize) -> Option<Index<Name>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Perform an index bounds check on a whole range of indices.
+    pub fn check_range(&self, range: Range<usize>) -> Option<CheckedRange<Name>> {
+        if range.end > self.len() {
+            None
+        } else {
+            Some(CheckedRange {
+                range,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get an atomic without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Name>) -> &A {
+        unsafe {
+            self.inner.get_unchecked(index.index)
+        }
+    }
+
+    /// Loads a value without bounds checking. See [std
+    /// docs](std::sync::atomic::AtomicU64::load) for more information.
+    #[inline(always)]
+    pub fn load(&self, index: Index<Name>, order: Ordering) -> A::Value {
+        self.get(index).load(order)
+    }
+
+    /// Stores a value without bounds checking. See [std
+    /// docs](std::sync::atomic::AtomicU64::store) for more information.
+    #[inline(always)]
+    pub fn store(&self, index: Index<Name>, value: A::Value, order: Ordering) {
+        self.get(index).store(value, order)
+    }
+
+    /// Stores a value without bounds checking, returning the previous value. See [std
+    /// docs](std::sync::atomic::AtomicU64::swap) for more information.
+    #[inline(always)]
+    pub fn swap(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).swap(value, order)
+    }
+
+    /// Stores a value if the current value is ``current``, without bounds checking. See [std
+    /// docs](std::sync::atomic::AtomicU64::compare_exchange) for more information.
+    #[inline(always)]
+    pub fn compare_exchange(&self, index: Index<Name>, current: A::Value, new: A::Value, success: Ordering, failure: Ordering) -> Result<A::Value, A::Value> {
+        self.get(index).compare_exchange(current, new, success, failure)
+    }
+
+    /// Adds to the current value without bounds checking, returning the previous value. See [std
+    /// docs](std::sync::atomic::AtomicU64::fetch_add) for more information.
+    #[inline(always)]
+    pub fn fetch_add(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_add(value, order)
+    }
+
+    /// Subtracts from the current value without bounds checking, returning the previous value. See
+    /// [std docs](std::sync::atomic::AtomicU64::fetch_sub) for more information.
+    #[inline(always)]
+    pub fn fetch_sub(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_sub(value, order)
+    }
+
+    /// Bitwise "and" with the current value without bounds checking, returning the previous value.
+    /// See [std docs](std::sync::atomic::AtomicU64::fetch_and) for more information.
+    #[inline(always)]
+    pub fn fetch_and(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_and(value, order)
+    }
+
+    /// Bitwise "or" with the current value without bounds checking, returning the previous value.
+    /// See [std docs](std::sync::atomic::AtomicU64::fetch_or) for more information.
+    #[inline(always)]
+    pub fn fetch_or(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_or(value, order)
+    }
+
+    /// Bitwise "xor" with the current value without bounds checking, returning the previous value.
+    /// See [std docs](std::sync::atomic::AtomicU64::fetch_xor) for more information.
+    #[inline(always)]
+    pub fn fetch_xor(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_xor(value, order)
+    }
+
+    /// Stores the maximum of the current value and ``value`` without bounds checking, returning the
+    /// previous value. See [std docs](std::sync::atomic::AtomicU64::fetch_max) for more
+    /// information.
+    #[inline(always)]
+    pub fn fetch_max(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_max(value, order)
+    }
+
+    /// Stores the minimum of the current value and ``value`` without bounds checking, returning the
+    /// previous value. See [std docs](std::sync::atomic::AtomicU64::fetch_min) for more
+    /// information.
+    #[inline(always)]
+    pub fn fetch_min(&self, index: Index<Name>, value: A::Value, order: Ordering) -> A::Value {
+        self.get(index).fetch_min(value, order)
+    }
+
+    /// Appends an atomic to the back of a collection. See [std docs](std::vec::Vec::push) for
+    /// more information.
+    pub fn push(&mut self, value: A) {
+        self.inner.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicI32, AtomicUsize};
+    use std::thread;
+    use type_name_value::name;
+
+    #[test]
+    fn parallel_histogram() {
+        let data: Vec<usize> = (0..1000).map(|i| (i * 7) % 10).collect();
+
+        let buckets: Vec<AtomicUsize> = (0..10).map(|_| AtomicUsize::new(0)).collect();
+        let buckets = name!(buckets);
+        let buckets = FixedAtomicVec::fix(buckets);
+
+        let indices: Vec<_> = data.iter().map(|&d| buckets.check_index(d).unwrap()).collect();
+
+        thread::scope(|scope| {
+            for chunk in indices.chunks(100) {
+                let buckets = &buckets;
+                scope.spawn(move || {
+                    for &index in chunk {
+                        buckets.fetch_add(index, 1, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(buckets.into_values(), vec![100; 10]);
+    }
+
+    #[test]
+    fn other_operations() {
+        let v: Vec<AtomicI32> = vec![AtomicI32::new(5)];
+        let v = name!(v);
+        let v = FixedAtomicVec::fix(v);
+
+        let index = v.check_index(0).unwrap();
+        assert!(v.check_index(1).is_none());
+
+        assert_eq!(v.fetch_max(index, 10, Ordering::SeqCst), 5);
+        assert_eq!(v.fetch_min(index, -3, Ordering::SeqCst), 10);
+        assert_eq!(v.compare_exchange(index, 0, 1, Ordering::SeqCst, Ordering::SeqCst), Err(-3));
+        assert_eq!(v.swap(index, 6, Ordering::SeqCst), -3);
+        assert_eq!(v.fetch_xor(index, 3, Ordering::SeqCst), 6);
+        assert_eq!(v.load(index, Ordering::SeqCst), 5);
+    }
+}
@@ -0,0 +1,210 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ops::Range;
+
+use type_name_value::Named;
+
+use crate::{CheckedRange, Index};
+
+/// A ``FixedVec`` of ``Cell``'s, so that elements can be updated through a shared reference. Like
+/// ``Cell`` itself, a ``FixedCellVec`` can't be shared between threads.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![0, 1, 2];
+/// let v = name!(v);
+/// let v = FixedCellVec::fix(v);
+///
+/// let a = v.check_index(0).unwrap();
+/// let b = v.check_index(2).unwrap();
+///
+/// let (view_a, view_b) = (&v, &v);
+/// view_a.set(a, 5);
+/// view_b.set(b, view_a.get(a) + 1);
+///
+/// assert_eq!(v.unfix(), vec![5, 1, 6]);
+/// ```
+///
+/// ```compile_fail
+/// # use fixed_vec::*;
+/// let v = vec![0, 1, 2];
+/// let v = name!(v);
+/// let v = FixedCellVec::fix(v);
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| v.get(v.check_index(0).unwrap()));
+/// });
+/// ```
+#[derive(Derivative)]
+#[derivative(Debug(bound="A: Copy + std::fmt::Debug"))]
+pub struct FixedCellVec<A, Name> {
+    inner: Vec<Cell<A>>,
+    _phantom: PhantomData<Name>,
+}
+
+impl<A, Name> Deref for FixedCellVec<A, Name> {
+    type Target = [Cell<A>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<A, Name> FixedCellVec<A, Name> {
+    /// Create a ``FixedCellVec`` from a named ``Vec``. To use this method, first assign a name to
+    /// a ``Vec`` using ``name!()``.
+    pub fn fix(val: Named<Vec<A>, Name>) -> Self {
+        FixedCellVec {
+            inner: val.unname().into_iter().map(Cell::new).collect(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Unwrap's the inner values back into a ``Vec``. Since this takes ownership of the
+    /// ``FixedCellVec``, it indirectly invalidates all ``Index``'s with the same ``Name``.
+    pub fn unfix(self) -> Vec<A> {
+        self.inner.into_iter().map(Cell::into_inner).collect()
+    }
+
+    /// Perform an index bounds check. This is the only way to directly create an ``Index`` for a
+    /// ``FixedCellVec``.
+    pub fn check_index(&self, index: usize) -> Option<Index<Name>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Perform an index bounds check on a whole range of indices.
+    pub fn check_range(&self, range: Range<usize>) -> Option<CheckedRange<Name>> {
+        if range.end > self.len() {
+            None
+        } else {
+            Some(CheckedRange {
+                range,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get the ``Cell`` containing an element without bounds checking.
+    #[inline(always)]
+    pub fn get_cell(&self, index: Index<Name>) -> &Cell<A> {
+        unsafe {
+            self.inner.get_unchecked(index.index)
+        }
+    }
+
+    /// Get a copy of an element without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Name>) -> A
+    where
+        A: Copy,
+    {
+        self.get_cell(index).get()
+    }
+
+    /// Set an element without bounds checking.
+    #[inline(always)]
+    pub fn set(&self, index: Index<Name>, value: A) {
+        self.get_cell(index).set(value);
+    }
+
+    /// Replace an element without bounds checking, returning the old value.
+    #[inline(always)]
+    pub fn replace(&self, index: Index<Name>, value: A) -> A {
+        self.get_cell(index).replace(value)
+    }
+
+    /// Take an element without bounds checking, leaving ``Default::default()`` in its place.
+    #[inline(always)]
+    pub fn take(&self, index: Index<Name>) -> A
+    where
+        A: Default,
+    {
+        self.get_cell(index).take()
+    }
+
+    /// Swap two elements without bounds checking.
+    #[inline(always)]
+    pub fn swap(&self, a: Index<Name>, b: Index<Name>) {
+        self.get_cell(a).swap(self.get_cell(b));
+    }
+
+    /// Get a mutable reference to an element without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: Index<Name>) -> &mut A {
+        unsafe {
+            self.inner.get_unchecked_mut(index.index).get_mut()
+        }
+    }
+
+    /// Appends an element to the back of a collection. See [std docs](std::vec::Vec::push) for
+    /// more information.
+    pub fn push(&mut self, value: A) {
+        self.inner.push(Cell::new(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    // Each node stores its parent, or ``None`` for a root, and the size of its set
+    type Nodes<Name> = FixedCellVec<(Option<Index<Name>>, usize), Name>;
+
+    fn find<Name>(nodes: &Nodes<Name>, mut node: Index<Name>) -> Index<Name> {
+        while let Some(parent) = nodes.get(node).0 {
+            match nodes.get(parent).0 {
+                Some(grandparent) => {
+                    nodes.set(node, (Some(grandparent), nodes.get(node).1));
+                    node = grandparent;
+                }
+                None => return parent,
+            }
+        }
+        node
+    }
+
+    fn union<Name>(nodes: &Nodes<Name>, a: Index<Name>, b: Index<Name>) {
+        let (a, b) = (find(nodes, a), find(nodes, b));
+        if a == b {
+            return;
+        }
+        let (small, large) = if nodes.get(a).1 < nodes.get(b).1 { (a, b) } else { (b, a) };
+        let size = nodes.get(small).1 + nodes.get(large).1;
+        nodes.set(small, (Some(large), 0));
+        nodes.set(large, (None, size));
+    }
+
+    #[test]
+    fn union_find() {
+        let v = vec![(None, 1); 6];
+        let v = name!(v);
+        let nodes = FixedCellVec::fix(v);
+
+        let node = |i| nodes.check_index(i).unwrap();
+        for (a, b) in [(0, 1), (1, 2), (3, 4)] {
+            union(&nodes, node(a), node(b));
+        }
+
+        assert_eq!(find(&nodes, node(0)), find(&nodes, node(2)));
+        assert_eq!(find(&nodes, node(3)), find(&nodes, node(4)));
+        assert_ne!(find(&nodes, node(0)), find(&nodes, node(5)));
+        assert_eq!(nodes.get(find(&nodes, node(2))).1, 3);
+
+        nodes.swap(node(0), node(5));
+        assert_eq!(nodes.replace(node(0), (None, 10)), (None, 1));
+        assert_eq!(nodes.take(node(0)), (None, 10));
+        let sizes: Vec<_> = nodes.unfix().into_iter().map(|(_, size)| size).collect();
+        assert_eq!(sizes, vec![0, 0, 0, 2, 0, 3]);
+    }
+}
@@ -0,0 +1,287 @@
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use type_name_value::Named;
+
+use crate::{FixedVec, Index};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that can be used to store a ``CompactIndex``. This is implemented
+/// for ``u8``, ``u16``, ``u32``, ``u64`` and ``usize``, and can't be implemented outside of this
+/// crate.
+pub trait IndexWidth: Copy + Debug + Eq + Ord + Hash + private::Sealed {
+    /// Convert an index into this width, if it can be represented.
+    fn from_usize(index: usize) -> Option<Self>;
+
+    /// Convert an index of this width back into a ``usize``.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_index_width {
+    ($($width:ty),*) => {
+        $(
+            impl private::Sealed for $width {}
+
+            impl IndexWidth for $width {
+                #[inline(always)]
+                fn from_usize(index: usize) -> Option<Self> {
+                    <$width>::try_from(index).ok()
+                }
+
+                #[inline(always)]
+                fn to_usize(self) -> usize {
+                    // This can't truncate, since every value was created from a usize
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_index_width!(u8, u16, u32, u64, usize);
+
+/// A valid index into a ``FixedVec`` with name ``Name``, stored as a ``W`` instead of a ``usize``
+/// to save memory when many indices are stored at once. Converting to and from an ``Index`` is
+/// free apart from checking that the index fits in ``W``.
+///
+/// ``check_compact_index`` fails unless the whole length of the ``FixedVec`` fits in ``W``, but
+/// the ``FixedVec`` can still be pushed to afterwards, past the range of ``W``. To make ``push``
+/// refuse to grow beyond that range while compact indices are handed out, use a
+/// ``CompactFixedVec`` instead.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![0u8; 300];
+/// let v = name!(v);
+/// let v = FixedVec::fix(v);
+///
+/// let small: Index32<_> = v.check_compact_index(299).unwrap();
+/// assert_eq!(std::mem::size_of_val(&small), 4);
+/// assert_eq!(v.get(small.expand()), &0);
+///
+/// let tiny: Option<Index8<_>> = v.check_compact_index(299);
+/// assert!(tiny.is_none());
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound="W: IndexWidth"), Copy(bound="W: IndexWidth"), Debug(bound="W: IndexWidth"), PartialEq(bound="W: IndexWidth"), Eq(bound="W: IndexWidth"), Hash(bound="W: IndexWidth"), PartialOrd(bound="W: IndexWidth"), Ord(bound="W: IndexWidth"))]
+pub struct CompactIndex<Name, W> {
+    index: W,
+    _phantom: PhantomData<Name>,
+}
+
+/// A ``CompactIndex`` stored in a single byte.
+pub type Index8<Name> = CompactIndex<Name, u8>;
+/// A ``CompactIndex`` stored in two bytes.
+pub type Index16<Name> = CompactIndex<Name, u16>;
+/// A ``CompactIndex`` stored in four bytes.
+pub type Index32<Name> = CompactIndex<Name, u32>;
+
+impl<Name, W: IndexWidth> CompactIndex<Name, W> {
+    /// Convert this into a full width ``Index``.
+    #[inline(always)]
+    pub fn expand(self) -> Index<Name> {
+        Index {
+            index: self.index.to_usize(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The index as a ``W``.
+    pub fn get(self) -> W {
+        self.index
+    }
+}
+
+impl<Name, W: IndexWidth> From<CompactIndex<Name, W>> for Index<Name> {
+    fn from(index: CompactIndex<Name, W>) -> Self {
+        index.expand()
+    }
+}
+
+impl<Name> Index<Name> {
+    /// Convert this into a ``CompactIndex``, if it fits in ``W``.
+    #[inline(always)]
+    pub fn compact<W: IndexWidth>(self) -> Option<CompactIndex<Name, W>> {
+        W::from_usize(self.index).map(|index| {
+            CompactIndex {
+                index,
+                _phantom: PhantomData,
+            }
+        })
+    }
+}
+
+impl<A, Name> FixedVec<A, Name> {
+    /// Perform an index bounds check, creating a ``CompactIndex``. This fails if the index is out
+    /// of bounds, or if the length of the ``FixedVec`` doesn't fit in ``W``.
+    pub fn check_compact_index<W: IndexWidth>(&self, index: usize) -> Option<CompactIndex<Name, W>> {
+        W::from_usize(self.len())?;
+        self.check_index(index)?.compact()
+    }
+}
+
+/// A ``FixedVec`` whose length always fits in ``W``, so that every element can be reached
+/// through a ``CompactIndex<Name, W>``. Pushing an element that would make the length too large
+/// for ``W`` fails and gives the element back.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![0u8; 254];
+/// let v = name!(v);
+/// let mut v = CompactFixedVec::<_, _, u8>::fix(v).unwrap();
+///
+/// let last = v.push(1).unwrap();
+/// assert_eq!(last.get(), 254);
+/// assert_eq!(v[last], 1);
+///
+/// assert_eq!(v.push(2), Err(2));
+/// ```
+#[derive(Debug)]
+pub struct CompactFixedVec<A, Name, W> {
+    inner: FixedVec<A, Name>,
+    _phantom: PhantomData<W>,
+}
+
+impl<A, Name, W> Deref for CompactFixedVec<A, Name, W> {
+    type Target = [A];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<A, Name, W: IndexWidth> CompactFixedVec<A, Name, W> {
+    /// Create a ``CompactFixedVec`` from a named ``Vec``. If the length of the ``Vec`` doesn't fit
+    /// in ``W``, it is given back unchanged.
+    pub fn fix(val: Named<Vec<A>, Name>) -> Result<Self, Vec<A>> {
+        if W::from_usize(val.unname_ref().len()).is_some() {
+            Ok(CompactFixedVec {
+                inner: FixedVec::fix(val),
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(val.unname())
+        }
+    }
+
+    /// Unwrap the inner ``FixedVec``, lifting the limit on its length. Every ``CompactIndex``
+    /// stays valid in it, since a ``FixedVec`` never shrinks.
+    pub fn into_fixed_vec(self) -> FixedVec<A, Name> {
+        self.inner
+    }
+
+    /// Perform an index bounds check, creating a ``CompactIndex``.
+    pub fn check_index(&self, index: usize) -> Option<CompactIndex<Name, W>> {
+        self.inner.check_index(index)?.compact()
+    }
+
+    /// Get an element without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: CompactIndex<Name, W>) -> &A {
+        self.inner.get(index.expand())
+    }
+
+    /// Get a mutable reference to an element without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: CompactIndex<Name, W>) -> &mut A {
+        self.inner.get_mut(index.expand())
+    }
+
+    /// Append an element to the back, returning its ``CompactIndex``. If the new length wouldn't
+    /// fit in ``W``, the element is given back instead.
+    pub fn push(&mut self, value: A) -> Result<CompactIndex<Name, W>, A> {
+        if W::from_usize(self.inner.len() + 1).is_none() {
+            return Err(value);
+        }
+        let index = self.inner.len();
+        self.inner.push(value);
+        match self.check_index(index) {
+            Some(index) => Ok(index),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<A, Name, W: IndexWidth> std::ops::Index<CompactIndex<Name, W>> for CompactFixedVec<A, Name, W> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: CompactIndex<Name, W>) -> &A {
+        self.get(index)
+    }
+}
+
+impl<A, Name, W: IndexWidth> std::ops::IndexMut<CompactIndex<Name, W>> for CompactFixedVec<A, Name, W> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: CompactIndex<Name, W>) -> &mut A {
+        self.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+    use type_name_value::name;
+
+    #[test]
+    fn compact_widths() {
+        assert_eq!(size_of::<Index8<()>>(), 1);
+        assert_eq!(size_of::<Index16<()>>(), 2);
+        assert_eq!(size_of::<Index32<()>>(), 4);
+
+        let v: Vec<u32> = (0..65535).collect();
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        assert!(v.check_compact_index::<u16>(65534).is_some());
+        for i in 65535..70000 {
+            v.push(i);
+        }
+        assert!(v.check_compact_index::<u16>(0).is_none());
+        assert!(v.check_compact_index::<u32>(70000).is_none());
+
+        let index: Index32<_> = v.check_compact_index(69999).unwrap();
+        assert_eq!(index.get(), 69999);
+        *v.get_mut(index.into()) += 1;
+        assert_eq!(v.get(index.expand()), &70000);
+
+        let full = v.check_index(255).unwrap();
+        assert_eq!(full.compact::<u8>().unwrap().expand(), full);
+        assert!(v.check_index(256).unwrap().compact::<u8>().is_none());
+    }
+
+    #[test]
+    fn compact_fixed_vec_refuses_to_grow() {
+        let v = vec![1, 2, 3];
+        let v = name!(v);
+        assert!(CompactFixedVec::<_, _, u8>::fix(v).is_ok());
+
+        let v = vec![0; 256];
+        let v = name!(v);
+        assert_eq!(CompactFixedVec::<_, _, u8>::fix(v).err().map(|v| v.len()), Some(256));
+
+        let v = vec![];
+        let v = name!(v);
+        let mut v = CompactFixedVec::<_, _, u8>::fix(v).unwrap();
+        let indices: Vec<_> = (0..255).map(|i| v.push(i).unwrap()).collect();
+        assert_eq!(v.push(255), Err(255));
+        assert!(v.check_index(255).is_none());
+
+        v[indices[10]] += 1;
+        let mut v = v.into_fixed_vec();
+        v.push(255);
+        assert_eq!(v.get(indices[10].expand()), &11);
+        assert_eq!(v.len(), 256);
+    }
+}
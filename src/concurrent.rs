@@ -0,0 +1,275 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+use type_name_value::Named;
+
+use crate::Index;
+
+/// The number of bits needed to index into the first bucket.
+const FIRST_BUCKET_BITS: usize = 5;
+/// The length of the first bucket. Every bucket after it is twice as long as the one before.
+const FIRST_BUCKET_LEN: usize = 1 << FIRST_BUCKET_BITS;
+/// The number of buckets needed to store ``MAX_LEN`` elements.
+const BUCKETS: usize = usize::BITS as usize - FIRST_BUCKET_BITS;
+/// The maximum number of elements that can ever be pushed.
+const MAX_LEN: usize = usize::MAX - FIRST_BUCKET_LEN + 1;
+
+struct Slot<A> {
+    ready: AtomicBool,
+    value: UnsafeCell<MaybeUninit<A>>,
+}
+
+/// Find the bucket containing ``index``, and the position of ``index`` within that bucket.
+#[inline(always)]
+fn locate(index: usize) -> (usize, usize) {
+    let pos = index + FIRST_BUCKET_LEN;
+    let bucket = (usize::BITS - 1 - pos.leading_zeros()) as usize - FIRST_BUCKET_BITS;
+    (bucket, pos - (FIRST_BUCKET_LEN << bucket))
+}
+
+fn bucket_len(bucket: usize) -> usize {
+    FIRST_BUCKET_LEN << bucket
+}
+
+/// An append-only vector that can be pushed to from many threads at once without locking. Like a
+/// ``FixedVec``, a ``ConcurrentFixedVec`` never shrinks, so every ``Index`` it hands out stays
+/// valid. Elements are stored in buckets that are never reallocated, so references to elements
+/// stay valid while other threads keep pushing.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = name!(Vec::new());
+/// let v = ConcurrentFixedVec::fix(v);
+///
+/// let indices: Vec<_> = std::thread::scope(|scope| {
+///     let handles: Vec<_> = (0..4).map(|i| {
+///         let v = &v;
+///         scope.spawn(move || v.push(i))
+///     }).collect();
+///     handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+/// });
+///
+/// for (i, index) in indices.into_iter().enumerate() {
+///     assert_eq!(v.get(index), &i);
+/// }
+/// ```
+pub struct ConcurrentFixedVec<A, Name> {
+    buckets: [AtomicPtr<Slot<A>>; BUCKETS],
+    reserved: AtomicUsize,
+    _phantom: PhantomData<(A, Name)>,
+}
+
+unsafe impl<A: Send, Name> Send for ConcurrentFixedVec<A, Name> {}
+unsafe impl<A: Send + Sync, Name> Sync for ConcurrentFixedVec<A, Name> {}
+
+impl<A, Name> ConcurrentFixedVec<A, Name> {
+    /// Create a ``ConcurrentFixedVec`` from a named ``Vec``. To use this method, first assign a
+    /// name to a ``Vec`` using ``name!()``.
+    pub fn fix(val: Named<Vec<A>, Name>) -> Self {
+        let v = ConcurrentFixedVec {
+            buckets: [(); BUCKETS].map(|_| AtomicPtr::new(ptr::null_mut())),
+            reserved: AtomicUsize::new(0),
+            _phantom: PhantomData,
+        };
+        for elem in val.unname() {
+            v.push(elem);
+        }
+        v
+    }
+
+    /// Move all the elements back into a ``Vec``. Since this takes ownership of the
+    /// ``ConcurrentFixedVec``, it indirectly invalidates all ``Index``'s with the same ``Name``.
+    pub fn unfix(mut self) -> Vec<A> {
+        let len = *self.reserved.get_mut();
+        let mut result = Vec::with_capacity(len);
+        for index in 0..len {
+            let (bucket, offset) = locate(index);
+            let slot = unsafe { &mut *self.buckets[bucket].get_mut().add(offset) };
+            if *slot.ready.get_mut() {
+                *slot.ready.get_mut() = false;
+                result.push(unsafe { slot.value.get_mut().assume_init_read() });
+            }
+        }
+        result
+    }
+
+    /// The number of elements that have been pushed, including any pushes that are still in
+    /// progress on other threads.
+    pub fn len(&self) -> usize {
+        self.reserved.load(Ordering::Acquire)
+    }
+
+    /// Returns ``true`` if nothing has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the bucket with the given number, allocating it if no other thread has yet.
+    fn bucket(&self, bucket: usize) -> *mut Slot<A> {
+        let existing = self.buckets[bucket].load(Ordering::Acquire);
+        if !existing.is_null() {
+            return existing;
+        }
+        let slots: Box<[Slot<A>]> = (0..bucket_len(bucket)).map(|_| Slot {
+            ready: AtomicBool::new(false),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }).collect();
+        let new = Box::into_raw(slots) as *mut Slot<A>;
+        match self.buckets[bucket].compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => new,
+            Err(existing) => {
+                // Another thread allocated this bucket first
+                unsafe {
+                    drop(Box::from_raw(ptr::slice_from_raw_parts_mut(new, bucket_len(bucket))));
+                }
+                existing
+            }
+        }
+    }
+
+    /// Appends an element to the back of the ``ConcurrentFixedVec``, returning its ``Index``. This
+    /// can be called from many threads at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of elements would overflow a ``usize``.
+    pub fn push(&self, value: A) -> Index<Name> {
+        let index = self.reserved.fetch_add(1, Ordering::AcqRel);
+        assert!(index < MAX_LEN, "capacity overflow");
+        let (bucket, offset) = locate(index);
+        unsafe {
+            let slot = &*self.bucket(bucket).add(offset);
+            // This thread is the only one that reserved this slot
+            (*slot.value.get()).write(value);
+            slot.ready.store(true, Ordering::Release);
+        }
+        Index {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Perform an index bounds check. This fails if the element at ``index`` is still being
+    /// pushed by another thread.
+    pub fn check_index(&self, index: usize) -> Option<Index<Name>> {
+        if self.len() <= index {
+            return None;
+        }
+        let (bucket, offset) = locate(index);
+        let slots = self.buckets[bucket].load(Ordering::Acquire);
+        if slots.is_null() || !unsafe { &*slots.add(offset) }.ready.load(Ordering::Acquire) {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get an element of the ``ConcurrentFixedVec`` without bounds checking or locking. This is
+    /// safe because an ``Index`` is only created once its element has been written.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Name>) -> &A {
+        let (bucket, offset) = locate(index.index);
+        unsafe {
+            let slot = &*self.buckets[bucket].load(Ordering::Acquire).add(offset);
+            (*slot.value.get()).assume_init_ref()
+        }
+    }
+
+    /// Returns an iterator over every element whose push has finished, in order, along with its
+    /// ``Index``.
+    pub fn iter(&self) -> impl Iterator<Item = (Index<Name>, &A)> {
+        (0..self.len()).filter_map(move |index| {
+            self.check_index(index).map(|index| (index, self.get(index)))
+        })
+    }
+}
+
+impl<A, Name> Drop for ConcurrentFixedVec<A, Name> {
+    fn drop(&mut self) {
+        for bucket in 0..BUCKETS {
+            let slots = *self.buckets[bucket].get_mut();
+            if slots.is_null() {
+                continue;
+            }
+            unsafe {
+                let mut slots = Box::from_raw(ptr::slice_from_raw_parts_mut(slots, bucket_len(bucket)));
+                for slot in slots.iter_mut() {
+                    if *slot.ready.get_mut() {
+                        slot.value.get_mut().assume_init_drop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<A: fmt::Debug, Name> fmt::Debug for ConcurrentFixedVec<A, Name> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(|(_, value)| value)).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use type_name_value::name;
+
+    #[test]
+    fn locate_buckets() {
+        assert_eq!(locate(0), (0, 0));
+        assert_eq!(locate(FIRST_BUCKET_LEN - 1), (0, FIRST_BUCKET_LEN - 1));
+        assert_eq!(locate(FIRST_BUCKET_LEN), (1, 0));
+        assert_eq!(locate(3 * FIRST_BUCKET_LEN), (2, 0));
+        assert_eq!(locate(MAX_LEN - 1), (BUCKETS - 1, bucket_len(BUCKETS - 1) - 1));
+    }
+
+    #[test]
+    fn concurrent_push() {
+        let v = name!(vec![String::from("start")]);
+        let v = ConcurrentFixedVec::fix(v);
+
+        let all: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8).map(|t| {
+                let v = &v;
+                scope.spawn(move || {
+                    (0..1000).map(|i| (v.push(format!("{} {}", t, i)), t, i)).collect::<Vec<_>>()
+                })
+            }).collect();
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        });
+
+        assert_eq!(v.len(), 8001);
+        for (index, t, i) in all {
+            assert_eq!(v.get(index), &format!("{} {}", t, i));
+        }
+        assert_eq!(v.get(v.check_index(0).unwrap()), "start");
+        assert!(v.check_index(8001).is_none());
+
+        let v = v.unfix();
+        assert_eq!(v.len(), 8001);
+    }
+
+    #[test]
+    fn drops_elements() {
+        let counter = Arc::new(());
+        let v = name!(Vec::new());
+        let v = ConcurrentFixedVec::fix(v);
+        for _ in 0..100 {
+            v.push(counter.clone());
+        }
+        assert_eq!(Arc::strong_count(&counter), 101);
+        drop(v);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
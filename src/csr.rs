@@ -0,0 +1,161 @@
+use std::iter::Zip;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+use std::slice;
+
+use crate::{Brand, CheckedRange, FixedVec, Index};
+
+/// A row of a ``FixedCsr`` with rows named ``Rows``. This is the same type as ``Index<Rows>``.
+pub type RowIdx<Rows> = Index<Rows>;
+
+/// A sparse matrix in compressed sparse row format. The row pointers and column indices are
+/// checked once when the matrix is created, so iterating over a row and multiplying by a vector
+/// don't need any bounds checks. Columns are named after a ``FixedVec`` with name ``Cols``, and
+/// since a ``FixedVec`` never shrinks, the matrix can be multiplied by it at any time.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let x = vec![1.0, 2.0, 3.0];
+/// let x = name!(x);
+/// let x = FixedVec::fix(x);
+///
+/// // [[2, 0, 1],
+/// //  [0, 0, 0],
+/// //  [0, 3, 0]]
+/// Brand::with(|brand| {
+///     let m = FixedCsr::new(brand, vec![0, 2, 2, 3], vec![0, 2, 1], vec![2.0, 1.0, 3.0], &x).unwrap();
+///
+///     assert_eq!(m.spmv(&x), vec![5.0, 0.0, 6.0]);
+///     let last_row: Vec<_> = m.row(m.check_row(2).unwrap()).map(|(col, value)| (**col, *value)).collect();
+///     assert_eq!(last_row, vec![(1, 3.0)]);
+/// });
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound="T: Clone"), Debug(bound="T: std::fmt::Debug"))]
+pub struct FixedCsr<T, Rows, Cols> {
+    row_ptr: Vec<usize>,
+    col_idx: Vec<Index<Cols>>,
+    values: Vec<T>,
+    _phantom: PhantomData<Rows>,
+}
+
+impl<'id, T, Cols> FixedCsr<T, Brand<'id>, Cols> {
+    /// Create a ``FixedCsr`` from its row pointers, column indices and values. The rows are named
+    /// by a ``Brand`` token from ``Brand::with``, and every column index is checked against the
+    /// ``FixedVec`` ``cols``. Returns ``None`` if ``row_ptr`` doesn't start at 0, decreases, or
+    /// doesn't end at the number of values, or if any column index is out of bounds.
+    pub fn new<A>(
+        _rows: Brand<'id>,
+        row_ptr: Vec<usize>,
+        col_idx: Vec<usize>,
+        values: Vec<T>,
+        cols: &FixedVec<A, Cols>,
+    ) -> Option<Self> {
+        if row_ptr.first() != Some(&0)
+            || row_ptr.last() != Some(&values.len())
+            || col_idx.len() != values.len()
+            || row_ptr.windows(2).any(|w| w[0] > w[1])
+        {
+            return None;
+        }
+        let col_idx = col_idx.into_iter().map(|col| cols.check_index(col)).collect::<Option<_>>()?;
+        Some(FixedCsr {
+            row_ptr,
+            col_idx,
+            values,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<T, Rows, Cols> FixedCsr<T, Rows, Cols> {
+    /// Unwrap's the row pointers, column indices and values.
+    pub fn into_parts(self) -> (Vec<usize>, Vec<usize>, Vec<T>) {
+        let col_idx = self.col_idx.into_iter().map(|col| col.index).collect();
+        (self.row_ptr, col_idx, self.values)
+    }
+
+    /// The number of rows.
+    pub fn nrows(&self) -> usize {
+        self.row_ptr.len() - 1
+    }
+
+    /// The number of stored values.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Perform a bounds check on a row number.
+    pub fn check_row(&self, row: usize) -> Option<RowIdx<Rows>> {
+        if self.nrows() <= row {
+            None
+        } else {
+            Some(Index {
+                index: row,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns an iterator over every row.
+    pub fn rows(&self) -> CheckedRange<Rows> {
+        CheckedRange {
+            range: 0..self.nrows(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the stored values of a row along with their columns, without
+    /// bounds checking.
+    #[inline(always)]
+    pub fn row(&self, row: RowIdx<Rows>) -> Zip<slice::Iter<'_, Index<Cols>>, slice::Iter<'_, T>> {
+        unsafe {
+            let start = *self.row_ptr.get_unchecked(row.index);
+            let end = *self.row_ptr.get_unchecked(row.index + 1);
+            self.col_idx.get_unchecked(start..end).iter().zip(self.values.get_unchecked(start..end))
+        }
+    }
+
+    /// Multiply the matrix by the vector ``x``, without bounds checking.
+    pub fn spmv(&self, x: &FixedVec<T, Cols>) -> Vec<T>
+    where
+        T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    {
+        self.rows().map(|row| {
+            self.row(row).fold(T::default(), |sum, (&col, &value)| sum + value * *x.get(col))
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn validates_and_multiplies() {
+        let x = vec![1, -1];
+        let x = name!(x);
+        let x = FixedVec::fix(x);
+
+        let invalid = |row_ptr, col_idx, values: Vec<i32>| {
+            Brand::with(|brand| FixedCsr::new(brand, row_ptr, col_idx, values, &x).is_none())
+        };
+        assert!(invalid(vec![0, 1], vec![2], vec![1]));
+        assert!(invalid(vec![0, 2, 1], vec![0], vec![1]));
+        assert!(invalid(vec![1, 1], vec![0], vec![1]));
+        assert!(invalid(vec![], vec![], vec![]));
+
+        let parts = Brand::with(|brand| {
+            let m = FixedCsr::new(brand, vec![0, 2, 3], vec![0, 1, 1], vec![3, 4, 5], &x).unwrap();
+            assert_eq!(m.nrows(), 2);
+            assert_eq!(m.nnz(), 3);
+            assert!(m.check_row(2).is_none());
+            assert_eq!(m.spmv(&x), vec![-1, -5]);
+            m.into_parts()
+        });
+        assert_eq!(parts, (vec![0, 2, 3], vec![0, 1, 1], vec![3, 4, 5]));
+    }
+}
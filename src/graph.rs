@@ -0,0 +1,397 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::marker::PhantomData;
+use std::ops::{Add, Deref};
+
+use type_name_value::Named;
+
+use crate::{Brand, CheckedRange, FixedVec, Index};
+
+/// A node of a ``FixedGraph`` with name ``Name``. This is the same type as ``Index<Name>``.
+pub type NodeId<Name> = Index<Name>;
+
+/// A directed graph stored as adjacency lists. Nodes live in a ``FixedVec``, and every edge stores
+/// the ``NodeId`` of its target, so following an edge never needs a bounds check.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// Brand::with(|brand| {
+///     let mut graph = FixedGraph::new(brand);
+///
+///     let shirt = graph.add_node("shirt");
+///     let tie = graph.add_node("tie");
+///     let jacket = graph.add_node("jacket");
+///     graph.add_edge(shirt, tie, ());
+///     graph.add_edge(tie, jacket, ());
+///     graph.add_edge(shirt, jacket, ());
+///
+///     let order = graph.topological_sort().unwrap();
+///     assert_eq!(order, vec![shirt, tie, jacket]);
+///     assert_eq!(graph.bfs(shirt), vec![shirt, tie, jacket]);
+///     assert_eq!(graph.strongly_connected_components().len(), 3);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct FixedGraph<N, E, Name> {
+    nodes: FixedVec<N, Name>,
+    edges: Vec<Vec<(NodeId<Name>, E)>>,
+}
+
+/// A value for every node of a ``FixedGraph`` with name ``Name``, that can be indexed by a
+/// ``NodeId`` without bounds checking. The side table borrows the graph for the lifetime ``'a``
+/// so that no nodes can be added while it is alive.
+#[derive(Derivative)]
+#[derivative(Clone(bound="T: Clone"), Debug(bound="T: std::fmt::Debug"))]
+pub struct SideTable<'a, T, Name> {
+    values: Vec<T>,
+    _phantom: PhantomData<(&'a (), Name)>,
+}
+
+impl<'a, T, Name> Deref for SideTable<'a, T, Name> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<'a, T, Name> SideTable<'a, T, Name> {
+    /// Get the value of a node without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, node: NodeId<Name>) -> &T {
+        unsafe {
+            self.values.get_unchecked(node.index)
+        }
+    }
+
+    /// Get a mutable reference to the value of a node without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, node: NodeId<Name>) -> &mut T {
+        unsafe {
+            self.values.get_unchecked_mut(node.index)
+        }
+    }
+
+    /// Unwrap the values back into a ``Vec``, in node order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<'a, T, Name> std::ops::Index<NodeId<Name>> for SideTable<'a, T, Name> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, node: NodeId<Name>) -> &T {
+        self.get(node)
+    }
+}
+
+impl<'a, T, Name> std::ops::IndexMut<NodeId<Name>> for SideTable<'a, T, Name> {
+    #[inline(always)]
+    fn index_mut(&mut self, node: NodeId<Name>) -> &mut T {
+        self.get_mut(node)
+    }
+}
+
+impl<'id, N, E> FixedGraph<N, E, Brand<'id>> {
+    /// Create an empty ``FixedGraph``, named by a ``Brand`` token from ``Brand::with``.
+    pub fn new(_brand: Brand<'id>) -> Self {
+        FixedGraph {
+            nodes: unsafe {
+                // Brand<'id> is unique, since its only token is used up here
+                FixedVec::empty()
+            },
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N, E, Name> FixedGraph<N, E, Name> {
+    /// Create a ``FixedGraph`` without any edges from a named ``Vec`` of nodes.
+    pub fn fix(nodes: Named<Vec<N>, Name>) -> Self {
+        let nodes = FixedVec::fix(nodes);
+        let edges = nodes.iter().map(|_| Vec::new()).collect();
+        FixedGraph {
+            nodes,
+            edges,
+        }
+    }
+
+    /// Unwrap's the nodes and adjacency lists, with edge targets converted back into ``usize``'s.
+    pub fn unfix(self) -> (Vec<N>, Vec<Vec<(usize, E)>>) {
+        let edges = self.edges.into_iter().map(|edges| {
+            edges.into_iter().map(|(target, edge)| (target.index, edge)).collect()
+        }).collect();
+        (self.nodes.unfix(), edges)
+    }
+
+    /// The number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Perform a bounds check on a node number.
+    pub fn check_node(&self, node: usize) -> Option<NodeId<Name>> {
+        self.nodes.check_index(node)
+    }
+
+    /// Returns an iterator over every node in the graph.
+    pub fn nodes(&self) -> CheckedRange<Name> {
+        CheckedRange {
+            range: 0..self.nodes.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Adds a node to the graph, returning its ``NodeId``.
+    pub fn add_node(&mut self, value: N) -> NodeId<Name> {
+        let index = self.nodes.len();
+        self.nodes.push(value);
+        self.edges.push(Vec::new());
+        Index {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Adds a directed edge between two nodes.
+    pub fn add_edge(&mut self, from: NodeId<Name>, to: NodeId<Name>, value: E) {
+        unsafe {
+            self.edges.get_unchecked_mut(from.index).push((to, value));
+        }
+    }
+
+    /// Get the value of a node without bounds checking.
+    #[inline(always)]
+    pub fn node(&self, node: NodeId<Name>) -> &N {
+        self.nodes.get(node)
+    }
+
+    /// Get a mutable reference to the value of a node without bounds checking.
+    #[inline(always)]
+    pub fn node_mut(&mut self, node: NodeId<Name>) -> &mut N {
+        self.nodes.get_mut(node)
+    }
+
+    /// Get the outgoing edges of a node, along with their targets, without bounds checking.
+    #[inline(always)]
+    pub fn edges(&self, node: NodeId<Name>) -> &[(NodeId<Name>, E)] {
+        unsafe {
+            self.edges.get_unchecked(node.index)
+        }
+    }
+
+    /// Returns an iterator over the targets of the outgoing edges of a node.
+    pub fn neighbors(&self, node: NodeId<Name>) -> impl DoubleEndedIterator<Item = NodeId<Name>> + ExactSizeIterator + '_ {
+        self.edges(node).iter().map(|&(target, _)| target)
+    }
+
+    /// Create a ``SideTable`` with ``value`` for every node.
+    pub fn side_table<T: Clone>(&self, value: T) -> SideTable<'_, T, Name> {
+        SideTable {
+            values: vec![value; self.nodes.len()],
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The nodes reachable from ``start``, in breadth first order.
+    pub fn bfs(&self, start: NodeId<Name>) -> Vec<NodeId<Name>> {
+        let mut visited = self.side_table(false);
+        let mut queue = VecDeque::new();
+        let mut order = Vec::new();
+        visited[start] = true;
+        queue.push_back(start);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// The nodes reachable from ``start``, in depth first preorder.
+    pub fn dfs(&self, start: NodeId<Name>) -> Vec<NodeId<Name>> {
+        let mut visited = self.side_table(false);
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(node);
+            // Push in reverse so that the first edge is visited first
+            stack.extend(self.neighbors(node).rev().filter(|&next| !visited[next]));
+        }
+        order
+    }
+
+    /// Sort the nodes so that every edge goes from an earlier node to a later one. Returns
+    /// ``None`` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId<Name>>> {
+        let mut in_degree = self.side_table(0usize);
+        for node in self.nodes() {
+            for next in self.neighbors(node) {
+                in_degree[next] += 1;
+            }
+        }
+        let mut ready: VecDeque<_> = self.nodes().filter(|&node| in_degree[node] == 0).collect();
+        let mut order = Vec::with_capacity(self.node_count());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        if order.len() == self.node_count() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    /// Find the strongly connected components of the graph using Tarjan's algorithm. Components
+    /// are returned in reverse topological order, so every edge between two components goes from
+    /// a later component to an earlier one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId<Name>>> {
+        let mut number = self.side_table(None);
+        let mut low_link = self.side_table(0usize);
+        let mut on_stack = self.side_table(false);
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_number = 0;
+
+        for root in self.nodes() {
+            if number[root].is_some() {
+                continue;
+            }
+            // Each frame holds a node and the position of the next edge to follow
+            let mut frames = vec![(root, 0)];
+            number[root] = Some(next_number);
+            low_link[root] = next_number;
+            next_number += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(node, edge)) = frames.last() {
+                if let Some(&(next, _)) = self.edges(node).get(edge) {
+                    frames.last_mut().unwrap().1 += 1;
+                    match number[next] {
+                        None => {
+                            number[next] = Some(next_number);
+                            low_link[next] = next_number;
+                            next_number += 1;
+                            stack.push(next);
+                            on_stack[next] = true;
+                            frames.push((next, 0));
+                        }
+                        Some(n) if on_stack[next] => {
+                            low_link[node] = low_link[node].min(n);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                if let Some(&(parent, _)) = frames.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+                if Some(low_link[node]) == number[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Find the shortest distance from ``start`` to every node using Dijkstra's algorithm, where
+    /// ``weight`` gives the length of each edge. Nodes that can't be reached from ``start`` have
+    /// a distance of ``None``. Weights must not be negative, and ``W::default()`` must be zero.
+    pub fn dijkstra<W, F>(&self, start: NodeId<Name>, mut weight: F) -> SideTable<'_, Option<W>, Name>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+        F: FnMut(&E) -> W,
+    {
+        let mut distance = self.side_table(None);
+        let mut heap = BinaryHeap::new();
+        distance[start] = Some(W::default());
+        heap.push(Reverse((W::default(), start)));
+        while let Some(Reverse((dist, node))) = heap.pop() {
+            if distance[node] != Some(dist) {
+                // A shorter path to this node was already found
+                continue;
+            }
+            for (next, edge) in self.edges(node) {
+                let new_dist = dist + weight(edge);
+                let shorter = match distance[*next] {
+                    Some(old) => new_dist < old,
+                    None => true,
+                };
+                if shorter {
+                    distance[*next] = Some(new_dist);
+                    heap.push(Reverse((new_dist, *next)));
+                }
+            }
+        }
+        distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn traversals() {
+        let v = name!(vec!['a', 'b', 'c', 'd', 'e']);
+        let mut graph = FixedGraph::fix(v);
+        let node = |i| graph.check_node(i).unwrap();
+        let (a, b, c, d, e) = (node(0), node(1), node(2), node(3), node(4));
+        for (from, to, weight) in [(a, b, 4), (a, c, 1), (c, b, 2), (b, d, 5), (c, d, 8)] {
+            graph.add_edge(from, to, weight);
+        }
+
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph.bfs(a), vec![a, b, c, d]);
+        assert_eq!(graph.dfs(a), vec![a, b, d, c]);
+        assert_eq!(graph.topological_sort().unwrap(), vec![a, e, c, b, d]);
+
+        let distance = graph.dijkstra(a, |&weight| weight);
+        assert_eq!(distance.into_vec(), vec![Some(0), Some(3), Some(1), Some(8), None]);
+
+        graph.add_edge(d, a, 1);
+        assert!(graph.topological_sort().is_none());
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[1], vec![e]);
+        let mut cycle = components[0].clone();
+        cycle.sort();
+        assert_eq!(cycle, vec![a, b, c, d]);
+        assert_eq!(graph.node(cycle[2]), &'c');
+    }
+}
@@ -0,0 +1,476 @@
+use std::iter::StepBy;
+use std::marker::PhantomData;
+use std::ops::{Deref, Range};
+use std::slice;
+
+use type_name_value::Named;
+
+use crate::{Brand, FixedVec, Index};
+
+/// A valid row of a ``FixedGrid`` with name ``Name``. This cannot be created except through the
+/// ``check_row`` method of the same ``FixedGrid``, or by moving from another valid row.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""), PartialOrd(bound=""), Ord(bound=""))]
+pub struct Row<Name> {
+    index: usize,
+    _phantom: PhantomData<Name>,
+}
+
+/// A valid column of a ``FixedGrid`` with name ``Name``. This cannot be created except through the
+/// ``check_col`` method of the same ``FixedGrid``, or by moving from another valid column.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""), PartialOrd(bound=""), Ord(bound=""))]
+pub struct Col<Name> {
+    index: usize,
+    _phantom: PhantomData<Name>,
+}
+
+impl<Name> Deref for Row<Name> {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+impl<Name> Deref for Col<Name> {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+fn row<Name>(index: usize) -> Row<Name> {
+    Row {
+        index,
+        _phantom: PhantomData,
+    }
+}
+
+fn col<Name>(index: usize) -> Col<Name> {
+    Col {
+        index,
+        _phantom: PhantomData,
+    }
+}
+
+/// A 2D grid stored in row-major order in a ``FixedVec``. Rows and columns are checked separately
+/// with ``check_row`` and ``check_col``, and every ``(Row, Col)`` pair can then be used to index
+/// the grid without bounds checking. The dimensions of a ``FixedGrid`` never change.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![0; 12];
+/// let v = name!(v);
+/// let mut grid = FixedGrid::fix(v, 4, 3).unwrap();
+///
+/// for r in grid.rows() {
+///     for c in grid.cols() {
+///         grid[(r, c)] = *r * 10 + *c;
+///     }
+/// }
+///
+/// let r = grid.check_row(1).unwrap();
+/// let c = grid.check_col(3).unwrap();
+/// assert_eq!(grid.row(r), &[10, 11, 12, 13]);
+/// assert_eq!(grid.col(c).copied().collect::<Vec<_>>(), vec![3, 13, 23]);
+/// assert_eq!(grid[(grid.up(r).unwrap(), c)], 3);
+/// assert!(grid.right(c).is_none());
+/// assert_eq!(grid[(r, grid.right_clamped(c))], 13);
+/// ```
+#[derive(Debug)]
+pub struct FixedGrid<A, Name> {
+    inner: FixedVec<A, Name>,
+    width: usize,
+    height: usize,
+}
+
+impl<A, Name> FixedGrid<A, Name> {
+    /// Create a ``FixedGrid`` from a named ``Vec`` in row-major order. If the length of the
+    /// ``Vec`` isn't ``width * height``, it is given back unchanged.
+    pub fn fix(val: Named<Vec<A>, Name>, width: usize, height: usize) -> Result<Self, Vec<A>> {
+        if width.checked_mul(height) == Some(val.unname_ref().len()) {
+            Ok(FixedGrid {
+                inner: FixedVec::fix(val),
+                width,
+                height,
+            })
+        } else {
+            Err(val.unname())
+        }
+    }
+
+    /// Unwrap the elements back into a ``Vec`` in row-major order. Since this takes ownership of
+    /// the ``FixedGrid``, it indirectly invalidates all ``Row``'s and ``Col``'s with the same
+    /// ``Name``.
+    pub fn unfix(self) -> Vec<A> {
+        self.inner.unfix()
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Perform a bounds check on a row number.
+    pub fn check_row(&self, index: usize) -> Option<Row<Name>> {
+        if index < self.height {
+            Some(row(index))
+        } else {
+            None
+        }
+    }
+
+    /// Perform a bounds check on a column number.
+    pub fn check_col(&self, index: usize) -> Option<Col<Name>> {
+        if index < self.width {
+            Some(col(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every row, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Row<Name>> + ExactSizeIterator {
+        (0..self.height).map(row)
+    }
+
+    /// Returns an iterator over every column, from left to right.
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = Col<Name>> + ExactSizeIterator {
+        (0..self.width).map(col)
+    }
+
+    /// Convert a position into an ``Index`` into the underlying ``FixedVec``.
+    #[inline(always)]
+    pub fn flat_index(&self, (r, c): (Row<Name>, Col<Name>)) -> Index<Name> {
+        Index {
+            index: r.index * self.width + c.index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get an element of the ``FixedGrid`` without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, pos: (Row<Name>, Col<Name>)) -> &A {
+        self.inner.get(self.flat_index(pos))
+    }
+
+    /// Get a mutable reference to an element of the ``FixedGrid`` without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, pos: (Row<Name>, Col<Name>)) -> &mut A {
+        let index = self.flat_index(pos);
+        self.inner.get_mut(index)
+    }
+
+    /// Get a whole row as a slice without bounds checking.
+    #[inline(always)]
+    pub fn row(&self, r: Row<Name>) -> &[A] {
+        let start = r.index * self.width;
+        unsafe {
+            self.inner.get_unchecked(start..start + self.width)
+        }
+    }
+
+    /// Get a whole row as a mutable slice without bounds checking.
+    #[inline(always)]
+    pub fn row_mut(&mut self, r: Row<Name>) -> &mut [A] {
+        let start = r.index * self.width;
+        let width = self.width;
+        unsafe {
+            self.inner.as_mut_slice().get_unchecked_mut(start..start + width)
+        }
+    }
+
+    /// Where a column starts in the buffer. A grid with no rows still has columns, so the start is
+    /// clamped to keep it in bounds of the empty buffer.
+    fn col_start(&self, c: Col<Name>) -> usize {
+        c.index.min(self.inner.len())
+    }
+
+    /// Returns an iterator over a column from top to bottom, without bounds checking.
+    #[inline(always)]
+    pub fn col(&self, c: Col<Name>) -> StepBy<slice::Iter<'_, A>> {
+        let start = self.col_start(c);
+        unsafe {
+            self.inner.get_unchecked(start..).iter().step_by(self.width)
+        }
+    }
+
+    /// Returns an iterator over mutable references to a column from top to bottom, without bounds
+    /// checking.
+    #[inline(always)]
+    pub fn col_mut(&mut self, c: Col<Name>) -> StepBy<slice::IterMut<'_, A>> {
+        let start = self.col_start(c);
+        let width = self.width;
+        unsafe {
+            self.inner.as_mut_slice().get_unchecked_mut(start..).iter_mut().step_by(width)
+        }
+    }
+
+    /// The row above ``r``, if there is one.
+    pub fn up(&self, r: Row<Name>) -> Option<Row<Name>> {
+        r.index.checked_sub(1).map(row)
+    }
+
+    /// The row below ``r``, if there is one.
+    pub fn down(&self, r: Row<Name>) -> Option<Row<Name>> {
+        self.check_row(r.index + 1)
+    }
+
+    /// The column to the left of ``c``, if there is one.
+    pub fn left(&self, c: Col<Name>) -> Option<Col<Name>> {
+        c.index.checked_sub(1).map(col)
+    }
+
+    /// The column to the right of ``c``, if there is one.
+    pub fn right(&self, c: Col<Name>) -> Option<Col<Name>> {
+        self.check_col(c.index + 1)
+    }
+
+    /// The row above ``r``, or ``r`` itself if it is the top row.
+    pub fn up_clamped(&self, r: Row<Name>) -> Row<Name> {
+        self.up(r).unwrap_or(r)
+    }
+
+    /// The row below ``r``, or ``r`` itself if it is the bottom row.
+    pub fn down_clamped(&self, r: Row<Name>) -> Row<Name> {
+        self.down(r).unwrap_or(r)
+    }
+
+    /// The column to the left of ``c``, or ``c`` itself if it is the leftmost column.
+    pub fn left_clamped(&self, c: Col<Name>) -> Col<Name> {
+        self.left(c).unwrap_or(c)
+    }
+
+    /// The column to the right of ``c``, or ``c`` itself if it is the rightmost column.
+    pub fn right_clamped(&self, c: Col<Name>) -> Col<Name> {
+        self.right(c).unwrap_or(c)
+    }
+
+    /// Create a view of a rectangle of the ``FixedGrid``, with its own ``Row``'s and ``Col``'s,
+    /// and pass it to ``f``. The view is named with a fresh ``Brand``. Returns ``None`` without
+    /// calling ``f`` if either range is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// let v: Vec<_> = (0..16).collect();
+    /// let v = name!(v);
+    /// let grid = FixedGrid::fix(v, 4, 4).unwrap();
+    ///
+    /// let (r, c) = grid.sub_grid(1..3, 2..4, |sub| {
+    ///     let r = sub.check_row(1).unwrap();
+    ///     let c = sub.check_col(0).unwrap();
+    ///
+    ///     assert_eq!(sub[(r, c)], 10);
+    ///     assert_eq!(sub.row(r), &[10, 11]);
+    ///     (sub.row_to_parent(r), sub.col_to_parent(c))
+    /// }).unwrap();
+    ///
+    /// assert_eq!(grid[(r, c)], 10);
+    /// ```
+    pub fn sub_grid<'b, R, F>(&'b self, rows: Range<usize>, cols: Range<usize>, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedSubGrid<'b, A, Name, Brand<'id>>) -> R,
+    {
+        if rows.start > rows.end || rows.end > self.height || cols.start > cols.end || cols.end > self.width {
+            return None;
+        }
+        Some(f(FixedSubGrid {
+            inner: &self.inner,
+            stride: self.width,
+            top: rows.start,
+            left: cols.start,
+            width: cols.len(),
+            height: rows.len(),
+            _phantom: PhantomData,
+        }))
+    }
+}
+
+impl<A, Name> std::ops::Index<(Row<Name>, Col<Name>)> for FixedGrid<A, Name> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, pos: (Row<Name>, Col<Name>)) -> &A {
+        self.get(pos)
+    }
+}
+
+impl<A, Name> std::ops::IndexMut<(Row<Name>, Col<Name>)> for FixedGrid<A, Name> {
+    #[inline(always)]
+    fn index_mut(&mut self, pos: (Row<Name>, Col<Name>)) -> &mut A {
+        self.get_mut(pos)
+    }
+}
+
+/// A rectangular view into a ``FixedGrid`` with name ``Name``. The view has its own name ``Sub``,
+/// so that its ``Row``'s and ``Col``'s can't be confused with the parent's.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound="A: std::fmt::Debug"))]
+pub struct FixedSubGrid<'a, A, Name, Sub> {
+    inner: &'a [A],
+    stride: usize,
+    top: usize,
+    left: usize,
+    width: usize,
+    height: usize,
+    _phantom: PhantomData<(Name, Sub)>,
+}
+
+impl<'a, A, Name, Sub> FixedSubGrid<'a, A, Name, Sub> {
+    /// The number of columns in the view.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows in the view.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Perform a bounds check on a row number of the view.
+    pub fn check_row(&self, index: usize) -> Option<Row<Sub>> {
+        if index < self.height {
+            Some(row(index))
+        } else {
+            None
+        }
+    }
+
+    /// Perform a bounds check on a column number of the view.
+    pub fn check_col(&self, index: usize) -> Option<Col<Sub>> {
+        if index < self.width {
+            Some(col(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over every row of the view, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = Row<Sub>> + ExactSizeIterator {
+        (0..self.height).map(row)
+    }
+
+    /// Returns an iterator over every column of the view, from left to right.
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = Col<Sub>> + ExactSizeIterator {
+        (0..self.width).map(col)
+    }
+
+    /// Convert a row of the view into a row of the parent ``FixedGrid``.
+    pub fn row_to_parent(&self, r: Row<Sub>) -> Row<Name> {
+        row(self.top + r.index)
+    }
+
+    /// Convert a column of the view into a column of the parent ``FixedGrid``.
+    pub fn col_to_parent(&self, c: Col<Sub>) -> Col<Name> {
+        col(self.left + c.index)
+    }
+
+    /// Get an element of the view without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, (r, c): (Row<Sub>, Col<Sub>)) -> &'a A {
+        unsafe {
+            self.inner.get_unchecked((self.top + r.index) * self.stride + self.left + c.index)
+        }
+    }
+
+    /// Get a row of the view as a slice without bounds checking.
+    #[inline(always)]
+    pub fn row(&self, r: Row<Sub>) -> &'a [A] {
+        let start = (self.top + r.index) * self.stride + self.left;
+        unsafe {
+            self.inner.get_unchecked(start..start + self.width)
+        }
+    }
+}
+
+impl<'a, A, Name, Sub> std::ops::Index<(Row<Sub>, Col<Sub>)> for FixedSubGrid<'a, A, Name, Sub> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, pos: (Row<Sub>, Col<Sub>)) -> &A {
+        self.get(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn blur() {
+        let v = vec![0, 0, 0, 0, 9, 0, 0, 0, 0];
+        let v = name!(v);
+        let grid = FixedGrid::fix(v, 3, 3).unwrap();
+
+        // Sum each cell with its clamped neighbours
+        let blurred: Vec<_> = grid.rows().flat_map(|r| {
+            let grid = &grid;
+            grid.cols().map(move |c| {
+                grid[(r, c)]
+                    + grid[(grid.up_clamped(r), c)]
+                    + grid[(grid.down_clamped(r), c)]
+                    + grid[(r, grid.left_clamped(c))]
+                    + grid[(r, grid.right_clamped(c))]
+            })
+        }).collect();
+        assert_eq!(blurred, vec![0, 9, 0, 9, 9, 9, 0, 9, 0]);
+
+        let v = name!(blurred);
+        assert!(FixedGrid::fix(v, 2, 4).is_err());
+    }
+
+    #[test]
+    fn rows_cols_and_views() {
+        let v: Vec<_> = (0..6).collect();
+        let v = name!(v);
+        let mut grid = FixedGrid::fix(v, 3, 2).unwrap();
+
+        let c = grid.check_col(1).unwrap();
+        for value in grid.col_mut(c) {
+            *value *= 10;
+        }
+        grid.row_mut(grid.check_row(0).unwrap()).reverse();
+        assert!(grid.check_row(2).is_none());
+        assert!(grid.up(grid.check_row(0).unwrap()).is_none());
+
+        let values = grid.sub_grid(1..2, 0..3, |sub| {
+            sub.rows().flat_map(|r| sub.row(r)).copied().collect::<Vec<_>>()
+        });
+        assert_eq!(values, Some(vec![3, 40, 5]));
+        assert!(grid.sub_grid(0..3, 0..1, |_| unreachable!()).is_none());
+
+        let empty = grid.sub_grid(2..2, 1..1, |empty| empty.rows().len() + empty.cols().len());
+        assert_eq!(empty, Some(0));
+
+        assert_eq!(grid.unfix(), vec![2, 10, 0, 3, 40, 5]);
+    }
+
+    #[test]
+    fn empty_grids() {
+        let v = name!(Vec::<u64>::new());
+        let mut no_rows = FixedGrid::fix(v, 5, 0).unwrap();
+        let c = no_rows.check_col(4).unwrap();
+        assert!(no_rows.check_row(0).is_none());
+        assert_eq!(no_rows.col(c).count(), 0);
+        assert_eq!(no_rows.col_mut(c).count(), 0);
+
+        let v = name!(Vec::<u64>::new());
+        let mut no_cols = FixedGrid::fix(v, 0, 5).unwrap();
+        let r = no_cols.check_row(4).unwrap();
+        assert!(no_cols.check_col(0).is_none());
+        assert!(no_cols.row(r).is_empty());
+        assert!(no_cols.row_mut(r).is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{Brand, FixedArena, Index};
+
+/// An interned string. A ``Symbol`` can only be created by an ``Interner`` with the same ``Name``,
+/// and stays valid for as long as the ``Interner`` does.
+pub type Symbol<Name> = Index<Name>;
+
+/// A string interner whose symbols can be resolved without failing or bounds checking. Strings are
+/// only ever added to an ``Interner``, so every ``Symbol`` it hands out stays valid.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// Brand::with(|brand| {
+///     let mut interner = Interner::new(brand);
+///
+///     let a = interner.intern("foo");
+///     let b = interner.intern("bar");
+///
+///     assert_eq!(interner.intern("foo"), a);
+///     assert_eq!(interner.get("bar"), Some(b));
+///     assert_eq!(interner.resolve(a), "foo");
+///     assert_eq!(interner.len(), 2);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Interner<Name> {
+    strings: FixedArena<Arc<str>, Name>,
+    symbols: HashMap<Arc<str>, Symbol<Name>>,
+}
+
+impl<'id> Interner<Brand<'id>> {
+    /// Create an empty ``Interner``, named by a ``Brand`` token from ``Brand::with``.
+    pub fn new(brand: Brand<'id>) -> Self {
+        Interner {
+            strings: FixedArena::new(brand),
+            symbols: HashMap::new(),
+        }
+    }
+}
+
+impl<Name> Interner<Name> {
+    /// The number of distinct strings that have been interned.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns ``true`` if nothing has been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Intern a string, returning the existing ``Symbol`` if it has been interned before.
+    pub fn intern(&mut self, string: &str) -> Symbol<Name> {
+        if let Some(&symbol) = self.symbols.get(string) {
+            return symbol;
+        }
+        let string: Arc<str> = Arc::from(string);
+        let symbol = self.strings.alloc(string.clone());
+        self.symbols.insert(string, symbol);
+        symbol
+    }
+
+    /// Look up the ``Symbol`` of a string without interning it.
+    pub fn get(&self, string: &str) -> Option<Symbol<Name>> {
+        self.symbols.get(string).copied()
+    }
+
+    /// Get the string of a ``Symbol`` without bounds checking.
+    #[inline(always)]
+    pub fn resolve(&self, symbol: Symbol<Name>) -> &str {
+        self.strings.get(symbol)
+    }
+
+    /// Returns an iterator over every interned string along with its ``Symbol``, in the order
+    /// they were first interned.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Symbol<Name>, &str)> + ExactSizeIterator {
+        self.strings.iter_indexed().map(|(symbol, string)| (symbol, &**string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_and_resolve() {
+        Brand::with(|brand| {
+            let mut interner = Interner::new(brand);
+
+            let words = "the quick fox jumps over the lazy fox";
+            let symbols: Vec<_> = words.split(' ').map(|word| interner.intern(word)).collect();
+
+            assert_eq!(interner.len(), 6);
+            assert_eq!(symbols[0], symbols[5]);
+            assert_eq!(symbols[2], symbols[7]);
+            assert!(interner.get("dog").is_none());
+
+            let resolved: Vec<_> = symbols.iter().map(|&symbol| interner.resolve(symbol)).collect();
+            assert_eq!(resolved.join(" "), words);
+
+            let order: Vec<_> = interner.iter().map(|(_, string)| string).collect();
+            assert_eq!(order, vec!["the", "quick", "fox", "jumps", "over", "lazy"]);
+        });
+    }
+}
@@ -0,0 +1,210 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use type_name_value::Named;
+
+use crate::{FixedVec, Index};
+
+/// A valid position ``v[i][j]`` in a ``FixedJagged`` with name ``Name``. This cannot be created
+/// except through the ``check_inner`` method of the same ``FixedJagged``, or by pushing to one of
+/// its inner ``Vec``'s.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""), PartialOrd(bound=""), Ord(bound=""))]
+pub struct InnerIdx<Name> {
+    outer: usize,
+    inner: usize,
+    _phantom: PhantomData<Name>,
+}
+
+impl<Name> InnerIdx<Name> {
+    /// The index of the inner ``Vec`` this position is in.
+    pub fn outer(&self) -> Index<Name> {
+        Index {
+            index: self.outer,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The position within the inner ``Vec``.
+    pub fn inner(&self) -> usize {
+        self.inner
+    }
+}
+
+/// A wrapper around a ``Vec<Vec<A>>`` where neither the outer ``Vec`` nor any of the inner
+/// ``Vec``'s can shrink, so that both ``Index``'s into the outer ``Vec`` and ``InnerIdx``'s into
+/// the inner ones always remain valid.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![vec![1, 2], vec![], vec![3]];
+/// let v = name!(v);
+/// let mut v = FixedJagged::fix(v);
+///
+/// let i = v.check_outer(1).unwrap();
+/// assert!(v.check_inner(i, 0).is_none());
+///
+/// let pushed = v.push_inner(i, 10);
+/// assert_eq!(v[pushed], 10);
+///
+/// let j = v.check_inner(v.check_outer(0).unwrap(), 1).unwrap();
+/// v[j] += 1;
+/// assert_eq!(v.unfix(), vec![vec![1, 3], vec![10], vec![3]]);
+/// ```
+#[derive(Debug)]
+pub struct FixedJagged<A, Name> {
+    inner: FixedVec<Vec<A>, Name>,
+}
+
+impl<A, Name> Deref for FixedJagged<A, Name> {
+    type Target = [Vec<A>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<A, Name> FixedJagged<A, Name> {
+    /// Create a ``FixedJagged`` from a named ``Vec<Vec<A>>``. To use this method, first assign a
+    /// name to the outer ``Vec`` using ``name!()``.
+    pub fn fix(val: Named<Vec<Vec<A>>, Name>) -> Self {
+        FixedJagged {
+            inner: FixedVec::fix(val),
+        }
+    }
+
+    /// Unwrap's the inner values back into a ``Vec<Vec<A>>``. Since this takes ownership of the
+    /// ``FixedJagged``, it indirectly invalidates all ``Index``'s and ``InnerIdx``'s with the same
+    /// ``Name``.
+    pub fn unfix(self) -> Vec<Vec<A>> {
+        self.inner.unfix()
+    }
+
+    /// Perform a bounds check on the outer ``Vec``.
+    pub fn check_outer(&self, index: usize) -> Option<Index<Name>> {
+        self.inner.check_index(index)
+    }
+
+    /// Perform a bounds check on the inner ``Vec`` at ``outer``. The outer index doesn't need to
+    /// be checked again.
+    pub fn check_inner(&self, outer: Index<Name>, index: usize) -> Option<InnerIdx<Name>> {
+        if self.inner(outer).len() <= index {
+            None
+        } else {
+            Some(InnerIdx {
+                outer: outer.index,
+                inner: index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns an iterator over every position in the inner ``Vec`` at ``outer``.
+    pub fn inner_indices(&self, outer: Index<Name>) -> impl DoubleEndedIterator<Item = InnerIdx<Name>> + ExactSizeIterator {
+        (0..self.inner(outer).len()).map(move |inner| InnerIdx {
+            outer: outer.index,
+            inner,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Get the inner ``Vec`` at ``outer`` as a slice, without bounds checking.
+    #[inline(always)]
+    pub fn inner(&self, outer: Index<Name>) -> &[A] {
+        self.inner.get(outer)
+    }
+
+    /// Get the inner ``Vec`` at ``outer`` as a mutable slice, without bounds checking. The slice
+    /// can't change the length of the inner ``Vec``.
+    #[inline(always)]
+    pub fn inner_mut(&mut self, outer: Index<Name>) -> &mut [A] {
+        self.inner.get_mut(outer)
+    }
+
+    /// Get an element without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: InnerIdx<Name>) -> &A {
+        unsafe {
+            self.inner.get_unchecked(index.outer).get_unchecked(index.inner)
+        }
+    }
+
+    /// Get a mutable reference to an element without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: InnerIdx<Name>) -> &mut A {
+        unsafe {
+            self.inner.as_mut_slice().get_unchecked_mut(index.outer).get_unchecked_mut(index.inner)
+        }
+    }
+
+    /// Appends a new inner ``Vec`` to the back of the outer ``Vec``, returning its ``Index``.
+    pub fn push_outer(&mut self, value: Vec<A>) -> Index<Name> {
+        let index = self.inner.len();
+        self.inner.push(value);
+        Index {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Appends an element to the back of the inner ``Vec`` at ``outer``, returning its
+    /// ``InnerIdx``.
+    pub fn push_inner(&mut self, outer: Index<Name>, value: A) -> InnerIdx<Name> {
+        let inner = self.inner.get_mut(outer);
+        inner.push(value);
+        InnerIdx {
+            outer: outer.index,
+            inner: inner.len() - 1,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A, Name> std::ops::Index<InnerIdx<Name>> for FixedJagged<A, Name> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: InnerIdx<Name>) -> &A {
+        self.get(index)
+    }
+}
+
+impl<A, Name> std::ops::IndexMut<InnerIdx<Name>> for FixedJagged<A, Name> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: InnerIdx<Name>) -> &mut A {
+        self.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn buckets() {
+        let v = vec![Vec::new(); 3];
+        let v = name!(v);
+        let mut buckets = FixedJagged::fix(v);
+
+        let mut pushed = Vec::new();
+        for value in [7, 3, 5, 9, 4] {
+            let bucket = buckets.check_outer(value % 3).unwrap();
+            pushed.push(buckets.push_inner(bucket, value));
+        }
+        let extra = buckets.push_outer(vec![100]);
+
+        assert_eq!(pushed.iter().map(|&index| buckets[index]).collect::<Vec<_>>(), vec![7, 3, 5, 9, 4]);
+        assert_eq!(pushed[3].outer(), buckets.check_outer(0).unwrap());
+        assert_eq!(pushed[3].inner(), 1);
+
+        let first = buckets.check_outer(1).unwrap();
+        buckets.inner_mut(first).reverse();
+        let first_values: Vec<_> = buckets.inner_indices(first).map(|index| buckets[index]).collect();
+        assert_eq!(first_values, vec![4, 7]);
+        assert_eq!(buckets.inner(extra), &[100]);
+        assert!(buckets.check_inner(extra, 1).is_none());
+    }
+}
@@ -10,6 +10,55 @@ extern crate type_name_value;
 
 pub use type_name_value::{Named, name};
 
+mod arena;
+mod array;
+mod atomic;
+mod cell;
+mod compact;
+mod concurrent;
+mod csr;
+mod graph;
+mod grid;
+mod interner;
+mod jagged;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod option_index;
+mod parallel;
+mod prefix;
+pub mod proof;
+mod ring;
+mod same_len;
+mod strided;
+mod sub_slice;
+mod table;
+mod tensor;
+
+use proof::{Property, Proof};
+
+pub use arena::FixedArena;
+pub use array::{Bounded, FixedArray, Idx, LenIs};
+pub use atomic::{AtomicInteger, FixedAtomicVec};
+pub use cell::FixedCellVec;
+pub use compact::{CompactFixedVec, CompactIndex, Index16, Index32, Index8, IndexWidth};
+pub use concurrent::ConcurrentFixedVec;
+pub use csr::{FixedCsr, RowIdx};
+pub use graph::{FixedGraph, NodeId, SideTable};
+pub use grid::{Col, FixedGrid, FixedSubGrid, Row};
+pub use interner::{Interner, Symbol};
+pub use jagged::{FixedJagged, InnerIdx};
+#[cfg(feature = "rayon")]
+pub use par_iter::ParCheckedRange;
+pub use option_index::OptionIndex;
+pub use parallel::ChunkName;
+pub use prefix::{prefix, Prefix};
+pub use ring::FixedRing;
+pub use same_len::{same_len, FixedPair, FixedVecs, SameLen};
+pub use strided::{FixedStrided, FixedStridedMut};
+pub use sub_slice::{FixedSubSlice, FixedSubSliceMut};
+pub use table::{Column, RowId};
+pub use tensor::{AxisIdx, FixedTensor, FixedTensorView, MultiIdx};
+
 /// A wrapper around a ``Vec`` that ensures that any valid indices will always remain valid. In
 /// practice, this means a ``FixedVec`` will never shrink in size (it can, however, grow in size).
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -90,6 +139,183 @@ impl<Name> ExactSizeIterator for CheckedRange<Name> {
     }
 }
 
+/// A name made from a fresh lifetime ``'id``. Methods like ``sub_slice`` pass their result to a
+/// closure that must work for any ``'id``, so every call gets a name that is different from every
+/// other one. This means an ``Index<Brand<'id>>`` can't be used with anything else, or leak out of
+/// the closure at all, even if the method is called in a loop.
+///
+/// A ``Brand`` value is also a token that empty containers like ``FixedArena`` are created from.
+/// Only one token exists for each ``'id``, and it is used up by the container, so no two
+/// containers can share a name.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// struct Node<Name> {
+///     parent: OptionIndex<Name>,
+/// }
+///
+/// let depth = Brand::with(|brand| {
+///     let mut arena = FixedArena::new(brand);
+///     let root = arena.alloc(Node { parent: OptionIndex::none() });
+///     let child = arena.alloc(Node { parent: root.into() });
+///
+///     let mut depth = 0;
+///     let mut node = arena[child].parent;
+///     while let Some(parent) = node.get() {
+///         depth += 1;
+///         node = arena[parent].parent;
+///     }
+///     depth
+/// });
+///
+/// assert_eq!(depth, 1);
+/// ```
+pub struct Brand<'id> {
+    _phantom: PhantomData<fn(&'id ()) -> &'id ()>,
+}
+
+impl<'id> Brand<'id> {
+    /// Create a token with a fresh lifetime and pass it to ``f``.
+    pub fn with<R, F>(f: F) -> R
+    where
+        F: for<'new> FnOnce(Brand<'new>) -> R,
+    {
+        f(Brand {
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// A proof that a ``FixedVec`` with name ``Name`` contains at least one element. This cannot be
+/// created except through the ``check_non_empty`` method of a ``FixedVec``. Since a ``FixedVec``
+/// never shrinks, the proof remains valid for as long as the ``FixedVec`` does.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![3, 1, 2];
+/// let v = name!(v);
+/// let v = FixedVec::fix(v);
+///
+/// let non_empty = v.check_non_empty().unwrap();
+///
+/// assert_eq!(v.first(non_empty), &3);
+/// assert_eq!(v.max_by_key(non_empty, |x| *x), &3);
+/// assert_eq!(v.reduce(non_empty, |a, b| a + b), 6);
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""))]
+pub struct NonEmpty<Name> {
+    _phantom: PhantomData<Name>,
+}
+
+// The methods using a ``NonEmpty`` proof, shared by ``FixedVec``, ``BorrowedFixedVec`` and
+// ``BorrowedMutFixedVec``. Each of them derefs to ``[A]`` and has ``get`` and ``is_empty``.
+macro_rules! non_empty_methods {
+    () => {
+        /// Check that the ``FixedVec`` is not empty. This is the only way to create a
+        /// ``NonEmpty``, which will share the same ``Name`` as the ``FixedVec``. Since a
+        /// ``FixedVec`` never shrinks, the proof can be used with methods like ``first`` and
+        /// ``max_by_key`` to get values directly instead of an ``Option``.
+        pub fn check_non_empty(&self) -> Option<NonEmpty<Name>> {
+            if self.is_empty() {
+                None
+            } else {
+                Some(NonEmpty {
+                    _phantom: PhantomData,
+                })
+            }
+        }
+
+        /// Get the first element of the ``FixedVec`` without checking that it exists.
+        #[inline(always)]
+        pub fn first(&self, non_empty: NonEmpty<Name>) -> &A {
+            self.get(self.first_index(non_empty))
+        }
+
+        /// Get the last element of the ``FixedVec`` without checking that it exists.
+        #[inline(always)]
+        pub fn last(&self, non_empty: NonEmpty<Name>) -> &A {
+            self.get(self.last_index(non_empty))
+        }
+
+        /// Get an ``Index`` to the first element of the ``FixedVec``.
+        pub fn first_index(&self, _non_empty: NonEmpty<Name>) -> Index<Name> {
+            Index {
+                index: 0,
+                _phantom: PhantomData,
+            }
+        }
+
+        /// Get an ``Index`` to the last element of the ``FixedVec``. Note that this is only the
+        /// last element at the time of calling, since the ``FixedVec`` might grow later on.
+        pub fn last_index(&self, _non_empty: NonEmpty<Name>) -> Index<Name> {
+            Index {
+                index: self.len() - 1,
+                _phantom: PhantomData,
+            }
+        }
+
+        /// Returns the element that gives the maximum value from the specified function. See [std
+        /// docs](std::iter::Iterator::max_by_key) for more information.
+        pub fn max_by_key<B: Ord, F: FnMut(&A) -> B>(&self, _non_empty: NonEmpty<Name>, mut f: F) -> &A {
+            unsafe {
+                // The NonEmpty proof guarantees there is at least one element
+                self.iter().max_by_key(|val| f(val)).unwrap_unchecked()
+            }
+        }
+
+        /// Returns the element that gives the minimum value from the specified function. See [std
+        /// docs](std::iter::Iterator::min_by_key) for more information.
+        pub fn min_by_key<B: Ord, F: FnMut(&A) -> B>(&self, _non_empty: NonEmpty<Name>, mut f: F) -> &A {
+            unsafe {
+                self.iter().min_by_key(|val| f(val)).unwrap_unchecked()
+            }
+        }
+
+        /// Reduces the elements to a single one, by repeatedly applying a reducing operation. See
+        /// [std docs](std::iter::Iterator::reduce) for more information.
+        pub fn reduce<F: FnMut(A, A) -> A>(&self, _non_empty: NonEmpty<Name>, f: F) -> A
+        where
+            A: Clone,
+        {
+            unsafe {
+                self.iter().cloned().reduce(f).unwrap_unchecked()
+            }
+        }
+    };
+}
+
+/// A proof that a ``FixedVec`` with name ``Name`` is sorted in ascending order. This cannot be
+/// created except through the ``check_sorted`` and ``sort_and_prove`` methods of a ``FixedVec``.
+/// Unlike ``Index`` and ``NonEmpty``, sortedness is not preserved by mutating the ``FixedVec``, so
+/// the proof borrows the ``FixedVec`` for the lifetime ``'a``. This means methods like ``get_mut``
+/// and ``push`` cannot be used while the proof is still alive.
+///
+/// # Example
+///
+/// ```compile_fail
+/// # use fixed_vec::*;
+/// let v = vec![1, 2, 3];
+/// let v = name!(v);
+/// let mut v = FixedVec::fix(v);
+///
+/// let sorted = v.check_sorted().unwrap();
+/// let index = v.check_index(0).unwrap();
+///
+/// *v.get_mut(index) = 10;
+///
+/// println!("{:?}", v.binary_search(sorted, &10));
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""))]
+pub struct Sorted<'a, Name> {
+    _phantom: PhantomData<(&'a (), Name)>,
+}
+
 impl<A, Name> FixedVec<A, Name> {
     /// Create a ``FixedVec`` from a named ``Vec``. To use this method, first assign a name to a
     /// ``Vec`` using ``name!()``.
@@ -98,6 +324,15 @@ impl<A, Name> FixedVec<A, Name> {
             inner: val,
         }
     }
+
+    /// Create an empty ``FixedVec``.
+    ///
+    /// # Safety
+    ///
+    /// ``Name`` must be unique, like a name created with ``name!()`` or a ``Brand`` token.
+    pub(crate) unsafe fn empty() -> Self {
+        FixedVec::fix(type_name_value::name(Vec::new()))
+    }
     
     /// Unwrap's the inner ``Vec`` so that it can be changed again, including its length. Since
     /// this takes ownership of the ``FixedVec``, it indirectly invalidates all ``Index``'s with
@@ -234,6 +469,178 @@ impl<A, Name> FixedVec<A, Name> {
         }
     }
 
+    non_empty_methods!();
+
+    /// Check that the ``FixedVec`` is sorted in ascending order. The returned ``Sorted`` proof can
+    /// be used with methods like ``binary_search`` and ``equal_range`` that rely on the
+    /// ``FixedVec`` being sorted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// let v = vec![1, 3, 3, 7];
+    /// let v = name!(v);
+    /// let v = FixedVec::fix(v);
+    ///
+    /// let sorted = v.check_sorted().unwrap();
+    ///
+    /// let index = v.binary_search(sorted, &7).unwrap();
+    /// assert_eq!(v.get(index), &7);
+    /// assert_eq!(v.equal_range(sorted, &3).len(), 2);
+    /// ```
+    pub fn check_sorted(&self) -> Option<Sorted<'_, Name>>
+    where
+        A: Ord,
+    {
+        if self.windows(2).all(|pair| pair[0] <= pair[1]) {
+            Some(Sorted {
+                _phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Sort the ``FixedVec`` in ascending order and return a ``Sorted`` proof. Since the proof
+    /// borrows the ``FixedVec``, a shared reference to it is returned alongside the proof.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// let v = vec![5, 2, 9];
+    /// let v = name!(v);
+    /// let mut v = FixedVec::fix(v);
+    ///
+    /// let (v, sorted) = v.sort_and_prove();
+    ///
+    /// assert_eq!(v.lower_bound(sorted, &6), 2);
+    /// ```
+    pub fn sort_and_prove(&mut self) -> (&Self, Sorted<'_, Name>)
+    where
+        A: Ord,
+    {
+        unsafe {
+            // Sorting will not change the length
+            self.inner.unname_ref_mut().sort();
+        }
+        (self, Sorted {
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Binary searches the sorted ``FixedVec`` for a given element. If the element is found, an
+    /// ``Index`` to it is returned, otherwise the position where it could be inserted is
+    /// returned. See [std docs](slice::binary_search) for more information.
+    pub fn binary_search(&self, _sorted: Sorted<'_, Name>, x: &A) -> Result<Index<Name>, usize>
+    where
+        A: Ord,
+    {
+        self.as_slice().binary_search(x).map(|index| {
+            Index {
+                index,
+                _phantom: PhantomData,
+            }
+        })
+    }
+
+    /// Returns the position of the first element that is not less than ``x``, or the length of
+    /// the ``FixedVec`` if there is no such element.
+    pub fn lower_bound(&self, _sorted: Sorted<'_, Name>, x: &A) -> usize
+    where
+        A: Ord,
+    {
+        self.partition_point(|elem| elem < x)
+    }
+
+    /// Returns the position of the first element that is greater than ``x``, or the length of
+    /// the ``FixedVec`` if there is no such element.
+    pub fn upper_bound(&self, _sorted: Sorted<'_, Name>, x: &A) -> usize
+    where
+        A: Ord,
+    {
+        self.partition_point(|elem| elem <= x)
+    }
+
+    /// Returns the range of all elements equal to ``x``. The returned ``CheckedRange`` is empty if
+    /// there are no such elements.
+    pub fn equal_range(&self, sorted: Sorted<'_, Name>, x: &A) -> CheckedRange<Name>
+    where
+        A: Ord,
+    {
+        CheckedRange {
+            range: self.lower_bound(sorted, x)..self.upper_bound(sorted, x),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the ranges of consecutive equal elements, in order. Every element of the
+    /// ``FixedVec`` belongs to exactly one of the returned ranges.
+    pub fn dedup_ranges(&self, _sorted: Sorted<'_, Name>) -> Vec<CheckedRange<Name>>
+    where
+        A: Ord,
+    {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for (end, pair) in self.windows(2).enumerate() {
+            if pair[0] != pair[1] {
+                ranges.push(CheckedRange {
+                    range: start..end + 1,
+                    _phantom: PhantomData,
+                });
+                start = end + 1;
+            }
+        }
+        if !self.is_empty() {
+            ranges.push(CheckedRange {
+                range: start..self.len(),
+                _phantom: PhantomData,
+            });
+        }
+        ranges
+    }
+
+    /// Merges the elements of this ``FixedVec`` and ``other``, which must both be sorted, and
+    /// appends the result to ``out``. The elements appended to ``out`` will be sorted, and the
+    /// merge is stable, preferring elements of ``self`` over equal elements of ``other``.
+    pub fn merge_into<OtherName>(
+        &self,
+        _sorted: Sorted<'_, Name>,
+        other: &FixedVec<A, OtherName>,
+        _other_sorted: Sorted<'_, OtherName>,
+        out: &mut Vec<A>,
+    ) where
+        A: Ord + Clone,
+    {
+        out.reserve(self.len() + other.len());
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            if r < l {
+                out.push((*r).clone());
+                right.next();
+            } else {
+                out.push((*l).clone());
+                left.next();
+            }
+        }
+        out.extend(left.cloned());
+        out.extend(right.cloned());
+    }
+
+    /// Check that a user defined ``Property`` holds for the ``FixedVec``. See the ``proof`` module
+    /// for more information. The returned ``Proof`` borrows the ``FixedVec``, so it can't be
+    /// mutated while the proof is alive.
+    pub fn check_property<P: Property<A>>(&self) -> Option<Proof<'_, Name, P>> {
+        if P::holds(self) {
+            // Property is an unsafe trait, so holds can be trusted
+            Some(unsafe { proof::axiom() })
+        } else {
+            None
+        }
+    }
+
     // Implementation of other normal Vec methods that preserve size
     
     /// Reserves capacity for at least ``additional`` more elements to be inserted in the given
@@ -353,6 +760,8 @@ impl<'a, A, Name> BorrowedFixedVec<'a, A, Name> {
             self.inner.unname_ref().get_unchecked(index.index)
         }
     }
+
+    non_empty_methods!();
 }
 
 /// A mutably borrowed version of a ``FixedVec``, created with a named ``&mut Vec<A>`` instead of a named ``Vec<A>``.
@@ -387,7 +796,7 @@ impl<'a, A, Name> Deref for BorrowedMutFixedVec<'a, A, Name> {
     type Target = Vec<A>;
 
     fn deref(&self) -> &Self::Target {
-        *self.inner.unname_ref()
+        self.inner.unname_ref()
     }
 }
 
@@ -458,6 +867,8 @@ impl<'a, A, Name> BorrowedMutFixedVec<'a, A, Name> {
         }
     }
 
+    non_empty_methods!();
+
     // Implementation of other normal Vec methods that preserve size
     
     /// Reserves capacity for at least ``additional`` more elements to be inserted in the given
@@ -601,4 +1012,53 @@ mod tests {
 
         assert_eq!(*v.unfix(), vec![11, 22, 33]);
     }
+
+    #[test]
+    fn non_empty() {
+        let v: Vec<u32> = vec![];
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        assert!(v.check_non_empty().is_none());
+
+        v.push(4);
+        v.push(7);
+        v.push(1);
+
+        let non_empty = v.check_non_empty().unwrap();
+
+        assert_eq!(v.first(non_empty), &4);
+        assert_eq!(v.last(non_empty), &1);
+        assert_eq!(*v.last_index(non_empty), 2);
+        assert_eq!(v.max_by_key(non_empty, |x| *x), &7);
+        assert_eq!(v.min_by_key(non_empty, |x| *x), &1);
+        assert_eq!(v.reduce(non_empty, |a, b| a * b), 28);
+    }
+
+    #[test]
+    fn sorted() {
+        let v = vec![4, 1, 4, 2, 9, 4];
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        assert!(v.check_sorted().is_none());
+
+        let (v, sorted) = v.sort_and_prove();
+
+        assert_eq!(v.binary_search(sorted, &3), Err(2));
+        assert_eq!(*v.binary_search(sorted, &9).unwrap(), 5);
+        assert_eq!(v.lower_bound(sorted, &4), 2);
+        assert_eq!(v.upper_bound(sorted, &4), 5);
+        assert_eq!(v.equal_range(sorted, &4).map(|i| *i).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(v.dedup_ranges(sorted).iter().map(|r| r.len()).collect::<Vec<_>>(), vec![1, 1, 3, 1]);
+
+        let other = vec![0, 4, 10];
+        let other = name!(other);
+        let other = FixedVec::fix(other);
+        let other_sorted = other.check_sorted().unwrap();
+
+        let mut out = vec![];
+        v.merge_into(sorted, &other, other_sorted, &mut out);
+        assert_eq!(out, vec![0, 1, 2, 4, 4, 4, 4, 9, 10]);
+    }
 }
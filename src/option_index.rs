@@ -0,0 +1,155 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::Index;
+
+/// The value used to represent ``None``. No ``Index`` can ever be ``usize::MAX``, since a ``Vec``
+/// can hold at most ``usize::MAX`` elements.
+const NONE: usize = usize::MAX;
+
+/// An ``Option<Index<Name>>`` that takes up the same space as an ``Index<Name>``, unlike an
+/// ``Option<Index<Name>>`` which needs an extra word for the tag. This is useful for "next"
+/// pointers in linked structures stored in a ``FixedVec``.
+///
+/// ``Option<Index<Name>>`` itself can't be made this small on stable Rust. Custom niches are
+/// unstable, so ``Index`` would have to store something like a ``NonZeroUsize`` holding
+/// ``index + 1``. But ``Index`` derefs to its ``usize`` through ``Deref<Target = usize>``, which
+/// must return a reference to a ``usize`` stored inside the ``Index``, and there wouldn't be one
+/// to point to. Instead, ``Index`` keeps a plain ``usize``, and this type uses ``usize::MAX`` as
+/// the sentinel for ``None``. ``From`` converts between ``OptionIndex<Name>`` and
+/// ``Option<Index<Name>>`` in both directions.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// struct Node<Name> {
+///     value: u32,
+///     next: OptionIndex<Name>,
+/// }
+///
+/// let v = vec![];
+/// let v = name!(v);
+/// let mut v = FixedVec::fix(v);
+///
+/// v.push(Node { value: 1, next: OptionIndex::none() });
+/// let first = v.check_index(0).unwrap();
+/// v.push(Node { value: 2, next: OptionIndex::some(first) });
+/// let second = v.check_index(1).unwrap();
+///
+/// let mut sum = 0;
+/// let mut node = OptionIndex::some(second);
+/// while let Some(index) = node.get() {
+///     sum += v.get(index).value;
+///     node = v.get(index).next;
+/// }
+///
+/// assert_eq!(sum, 3);
+/// assert_eq!(std::mem::size_of::<OptionIndex<()>>(), std::mem::size_of::<usize>());
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""))]
+pub struct OptionIndex<Name> {
+    index: usize,
+    _phantom: PhantomData<Name>,
+}
+
+impl<Name> OptionIndex<Name> {
+    /// An ``OptionIndex`` without an ``Index``.
+    pub fn none() -> Self {
+        OptionIndex {
+            index: NONE,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// An ``OptionIndex`` containing ``index``.
+    pub fn some(index: Index<Name>) -> Self {
+        OptionIndex {
+            index: index.index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns ``true`` if this contains an ``Index``.
+    pub fn is_some(&self) -> bool {
+        self.index != NONE
+    }
+
+    /// Returns ``true`` if this doesn't contain an ``Index``.
+    pub fn is_none(&self) -> bool {
+        self.index == NONE
+    }
+
+    /// Unpack this into an ``Option<Index<Name>>``.
+    #[inline(always)]
+    pub fn get(self) -> Option<Index<Name>> {
+        if self.is_none() {
+            None
+        } else {
+            Some(Index {
+                index: self.index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Take the ``Index`` out, leaving ``none()`` in its place.
+    pub fn take(&mut self) -> Option<Index<Name>> {
+        std::mem::take(self).get()
+    }
+}
+
+impl<Name> Default for OptionIndex<Name> {
+    fn default() -> Self {
+        OptionIndex::none()
+    }
+}
+
+impl<Name> fmt::Debug for OptionIndex<Name> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<Name> From<Index<Name>> for OptionIndex<Name> {
+    fn from(index: Index<Name>) -> Self {
+        OptionIndex::some(index)
+    }
+}
+
+impl<Name> From<Option<Index<Name>>> for OptionIndex<Name> {
+    fn from(index: Option<Index<Name>>) -> Self {
+        index.map_or_else(OptionIndex::none, OptionIndex::some)
+    }
+}
+
+impl<Name> From<OptionIndex<Name>> for Option<Index<Name>> {
+    fn from(index: OptionIndex<Name>) -> Self {
+        index.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FixedVec;
+    use type_name_value::name;
+
+    #[test]
+    fn round_trip() {
+        let v = vec![(); 3];
+        let v = name!(v);
+        let v = FixedVec::fix(v);
+
+        let index = v.check_index(2).unwrap();
+
+        let mut opt = OptionIndex::from(Some(index));
+        assert!(opt.is_some());
+        assert_eq!(opt.get(), Some(index));
+        assert_eq!(opt.take(), Some(index));
+        assert!(opt.is_none());
+        assert_eq!(Option::<Index<_>>::from(opt), None);
+        assert_eq!(format!("{:?}", OptionIndex::from(index)), format!("{:?}", Some(index)));
+    }
+}
@@ -0,0 +1,180 @@
+use std::marker::PhantomData;
+
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::prelude::*;
+
+use crate::{BorrowedMutFixedVec, CheckedRange, FixedVec, Index};
+
+/// A parallel iterator over the indices of a ``CheckedRange``, created by
+/// ``CheckedRange::into_par_iter``.
+pub struct ParCheckedRange<Name> {
+    range: CheckedRange<Name>,
+}
+
+impl<Name: Send> IntoParallelIterator for CheckedRange<Name> {
+    type Iter = ParCheckedRange<Name>;
+    type Item = Index<Name>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParCheckedRange {
+            range: self,
+        }
+    }
+}
+
+impl<Name: Send> ParallelIterator for ParCheckedRange<Name> {
+    type Item = Index<Name>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.range.len())
+    }
+}
+
+impl<Name: Send> IndexedParallelIterator for ParCheckedRange<Name> {
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self)
+    }
+}
+
+impl<Name: Send> Producer for ParCheckedRange<Name> {
+    type Item = Index<Name>;
+    type IntoIter = CheckedRange<Name>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.range
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let range = self.range.range;
+        let mid = range.start + index;
+        (
+            ParCheckedRange {
+                range: CheckedRange {
+                    range: range.start..mid,
+                    _phantom: self.range._phantom,
+                },
+            },
+            ParCheckedRange {
+                range: CheckedRange {
+                    range: mid..range.end,
+                    _phantom: self.range._phantom,
+                },
+            },
+        )
+    }
+}
+
+impl<A, Name> FixedVec<A, Name> {
+    /// Returns a parallel iterator over every element of the ``FixedVec`` along with its
+    /// ``Index``.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// use rayon::prelude::*;
+    ///
+    /// let v = vec![3, 1, 2];
+    /// let v = name!(v);
+    /// let v = FixedVec::fix(v);
+    ///
+    /// let (index, _) = v.par_iter_indexed().max_by_key(|(_, x)| **x).unwrap();
+    ///
+    /// assert_eq!(*index, 0);
+    /// ```
+    pub fn par_iter_indexed(&self) -> impl IndexedParallelIterator<Item = (Index<Name>, &A)>
+    where
+        A: Sync,
+        Name: Send,
+    {
+        let range = CheckedRange {
+            range: 0..self.len(),
+            _phantom: PhantomData,
+        };
+        range.into_par_iter().zip(self.as_slice().par_iter())
+    }
+
+    /// Returns a parallel iterator over mutable references to every element of the ``FixedVec``
+    /// along with its ``Index``.
+    pub fn par_iter_mut_indexed(&mut self) -> impl IndexedParallelIterator<Item = (Index<Name>, &mut A)>
+    where
+        A: Send,
+        Name: Send,
+    {
+        let range = CheckedRange {
+            range: 0..self.len(),
+            _phantom: PhantomData,
+        };
+        range.into_par_iter().zip(self.as_mut_slice().par_iter_mut())
+    }
+}
+
+impl<'a, A, Name> BorrowedMutFixedVec<'a, A, Name> {
+    /// Returns a parallel iterator over every element of the ``BorrowedMutFixedVec`` along with
+    /// its ``Index``.
+    pub fn par_iter_indexed(&self) -> impl IndexedParallelIterator<Item = (Index<Name>, &A)>
+    where
+        A: Sync,
+        Name: Send,
+    {
+        let range = CheckedRange {
+            range: 0..self.len(),
+            _phantom: PhantomData,
+        };
+        range.into_par_iter().zip(self.as_slice().par_iter())
+    }
+
+    /// Returns a parallel iterator over mutable references to every element of the
+    /// ``BorrowedMutFixedVec`` along with its ``Index``.
+    pub fn par_iter_mut_indexed(&mut self) -> impl IndexedParallelIterator<Item = (Index<Name>, &mut A)>
+    where
+        A: Send,
+        Name: Send,
+    {
+        let range = CheckedRange {
+            range: 0..self.len(),
+            _phantom: PhantomData,
+        };
+        range.into_par_iter().zip(self.as_mut_slice().par_iter_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn par_checked_range() {
+        let v = vec![1u64; 1000];
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        let range = v.check_range(100..900).unwrap();
+        let sum: u64 = range.into_par_iter().map(|i| *v.get(i)).sum();
+        assert_eq!(sum, 800);
+
+        let range = v.check_range(0..1000).unwrap();
+        let indices: Vec<_> = range.into_par_iter().rev().with_min_len(7).collect();
+        assert_eq!(*indices[0], 999);
+        assert_eq!(indices.len(), 1000);
+
+        v.par_iter_mut_indexed().for_each(|(i, x)| *x += *i as u64);
+        assert!(v.par_iter_indexed().all(|(i, x)| *x == *i as u64 + 1));
+    }
+}
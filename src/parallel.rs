@@ -0,0 +1,132 @@
+use std::marker::PhantomData;
+use std::thread;
+
+use crate::{BorrowedMutFixedVec, FixedSubSliceMut, FixedVec};
+
+/// The name given to each chunk by ``par_chunks_mut``. The lifetime ``'id`` is different for every
+/// chunk, so an ``Index`` created for one chunk can't be used with another chunk, or leak out of
+/// the closure at all.
+pub struct ChunkName<'id> {
+    _phantom: PhantomData<fn(&'id ()) -> &'id ()>,
+}
+
+/// Run ``f`` on every chunk of ``slice`` in parallel, spreading the chunks evenly over as many
+/// scoped threads as are available.
+fn par_chunks_mut<A, Name, F>(slice: &mut [A], chunk_size: usize, f: F)
+where
+    A: Send,
+    F: for<'id> Fn(FixedSubSliceMut<'_, A, Name, ChunkName<'id>>) + Sync,
+{
+    assert!(chunk_size != 0, "chunk size must be non-zero");
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut buckets: Vec<Vec<(usize, &mut [A])>> = (0..threads).map(|_| Vec::new()).collect();
+    for (i, chunk) in slice.chunks_mut(chunk_size).enumerate() {
+        buckets[i % threads].push((i * chunk_size, chunk));
+    }
+    let f = &f;
+    thread::scope(|scope| {
+        for bucket in buckets.into_iter().filter(|bucket| !bucket.is_empty()) {
+            scope.spawn(move || {
+                for (offset, chunk) in bucket {
+                    f(FixedSubSliceMut {
+                        inner: chunk,
+                        offset,
+                        _phantom: PhantomData,
+                    });
+                }
+            });
+        }
+    });
+}
+
+impl<A, Name> FixedVec<A, Name> {
+    /// Split the ``FixedVec`` into chunks of ``chunk_size`` elements (the last chunk may be
+    /// shorter), and run ``f`` on each of them in parallel. Each chunk is a ``FixedSubSliceMut``
+    /// with its own name, so ``Index``'s can be checked once per chunk and then used without
+    /// bounds checks. Chunks can still be converted back into ``Index``'s of the ``FixedVec`` with
+    /// ``to_parent``.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ``chunk_size`` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// let v = vec![1u64; 1000];
+    /// let v = name!(v);
+    /// let mut v = FixedVec::fix(v);
+    ///
+    /// v.par_chunks_mut(100, |mut chunk| {
+    ///     for i in chunk.check_range(0..chunk.len()).unwrap() {
+    ///         *chunk.get_mut(i) += *chunk.to_parent(i) as u64;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(v.iter().sum::<u64>(), 1000 + 999 * 1000 / 2);
+    /// ```
+    ///
+    /// Indices can't escape the chunk they were created for:
+    ///
+    /// ```compile_fail
+    /// # use fixed_vec::*;
+    /// let v = vec![1u64; 1000];
+    /// let v = name!(v);
+    /// let mut v = FixedVec::fix(v);
+    ///
+    /// let leaked = std::sync::Mutex::new(None);
+    ///
+    /// v.par_chunks_mut(100, |chunk| {
+    ///     *leaked.lock().unwrap() = chunk.check_index(0);
+    /// });
+    /// ```
+    pub fn par_chunks_mut<F>(&mut self, chunk_size: usize, f: F)
+    where
+        A: Send,
+        F: for<'id> Fn(FixedSubSliceMut<'_, A, Name, ChunkName<'id>>) + Sync,
+    {
+        par_chunks_mut(self.as_mut_slice(), chunk_size, f);
+    }
+}
+
+impl<'a, A, Name> BorrowedMutFixedVec<'a, A, Name> {
+    /// Split the ``BorrowedMutFixedVec`` into chunks of ``chunk_size`` elements, and run ``f`` on
+    /// each of them in parallel. See ``FixedVec::par_chunks_mut`` for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ``chunk_size`` is 0.
+    pub fn par_chunks_mut<F>(&mut self, chunk_size: usize, f: F)
+    where
+        A: Send,
+        F: for<'id> Fn(FixedSubSliceMut<'_, A, Name, ChunkName<'id>>) + Sync,
+    {
+        par_chunks_mut(self.as_mut_slice(), chunk_size, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use type_name_value::name;
+
+    #[test]
+    fn chunks_cover_vec() {
+        let mut v_ = vec![0usize; 103];
+        let v = name!(&mut v_);
+        let mut v = BorrowedMutFixedVec::fix(v);
+
+        let calls = AtomicUsize::new(0);
+        v.par_chunks_mut(10, |mut chunk| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            for i in chunk.check_range(0..chunk.len()).unwrap() {
+                *chunk.get_mut(i) = *chunk.to_parent(i);
+            }
+        });
+
+        assert_eq!(calls.into_inner(), 11);
+        assert_eq!(v_, (0..103).collect::<Vec<_>>());
+    }
+}
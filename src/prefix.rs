@@ -0,0 +1,120 @@
+use std::marker::PhantomData;
+
+use crate::{CheckedRange, FixedVec, Index, SameLen};
+
+/// A proof that the ``FixedVec`` with name ``Small`` is no longer than the ``FixedVec`` with name
+/// ``Big``, so that any ``Index`` into ``Small`` is also valid in ``Big``. Since ``Big`` never
+/// shrinks, it may keep growing, but ``Small`` is borrowed for the lifetime ``'a`` so that it can't
+/// grow past ``Big`` while the proof is alive.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""))]
+pub struct Prefix<'a, Small, Big> {
+    _phantom: PhantomData<(&'a (), Small, Big)>,
+}
+
+/// Check whether ``small`` is no longer than ``big``. This is the only way to directly create a
+/// ``Prefix``.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let table = vec![2, 4];
+/// let table = name!(table);
+/// let table = FixedVec::fix(table);
+///
+/// let buffer = vec![0u32; 100];
+/// let buffer = name!(buffer);
+/// let mut buffer = FixedVec::fix(buffer);
+///
+/// let proof = prefix(&table, &buffer).unwrap();
+///
+/// let index = table.check_index(1).unwrap();
+///
+/// *buffer.get_mut(index.transport(&proof)) = *table.get(index);
+/// ```
+pub fn prefix<'a, A, B, Small, Big>(small: &'a FixedVec<A, Small>, big: &FixedVec<B, Big>) -> Option<Prefix<'a, Small, Big>> {
+    if small.len() <= big.len() {
+        Some(Prefix {
+            _phantom: PhantomData,
+        })
+    } else {
+        None
+    }
+}
+
+impl<Small> Index<Small> {
+    /// Convert this ``Index`` into an ``Index`` of a ``FixedVec`` that is at least as long.
+    pub fn transport<Big>(self, _prefix: &Prefix<'_, Small, Big>) -> Index<Big> {
+        Index {
+            index: self.index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Small> CheckedRange<Small> {
+    /// Convert this ``CheckedRange`` into a ``CheckedRange`` of a ``FixedVec`` that is at least as
+    /// long.
+    pub fn transport<Big>(self, _prefix: &Prefix<'_, Small, Big>) -> CheckedRange<Big> {
+        CheckedRange {
+            range: self.range,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, N1, N2> SameLen<'a, N1, N2> {
+    /// Weaken this proof into a ``Prefix``.
+    pub fn prefix(self) -> Prefix<'a, N1, N2> {
+        Prefix {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+    use crate::same_len;
+
+    #[test]
+    fn transport() {
+        let small = vec![1, 2];
+        let small = name!(small);
+        let small = FixedVec::fix(small);
+
+        let big = vec![0; 4];
+        let big = name!(big);
+        let mut big = FixedVec::fix(big);
+
+        assert!(prefix(&big, &small).is_none());
+
+        let proof = prefix(&small, &big).unwrap();
+
+        for i in small.check_range(0..2).unwrap().transport(&proof) {
+            *big.get_mut(i) += 1;
+        }
+        big.push(5);
+
+        let index = small.check_index(1).unwrap();
+        assert_eq!(big.get(index.transport(&proof)), &1);
+        assert_eq!(big.unfix(), vec![1, 1, 0, 0, 5]);
+    }
+
+    #[test]
+    fn same_len_prefix() {
+        let a = vec![1, 2];
+        let a = name!(a);
+        let a = FixedVec::fix(a);
+
+        let b = vec![3, 4];
+        let b = name!(b);
+        let b = FixedVec::fix(b);
+
+        let proof = same_len(&a, &b).unwrap().prefix();
+        let index = a.check_index(0).unwrap();
+        assert_eq!(b.get(index.transport(&proof)), &3);
+    }
+}
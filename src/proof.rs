@@ -0,0 +1,209 @@
+//! A small framework for writing your own proofs about named values, in the style of the "Ghosts
+//! of Departed Proofs" paper. A ``Proof<'a, Name, P>`` is a zero-sized value showing that the
+//! property ``P`` holds for the value with name ``Name``. Proofs can be combined with ``and``,
+//! ``or_left`` / ``or_right`` and ``implies``, and new facts can be introduced with ``axiom``.
+//!
+//! Like ``Sorted``, a ``Proof`` borrows the value it talks about for the lifetime ``'a``, so that
+//! properties about the contents of a ``FixedVec`` can't be invalidated by mutating it while the
+//! proof is still alive.
+//!
+//! # Example
+//!
+//! ```
+//! # use fixed_vec::*;
+//! use fixed_vec::proof::{Property, Proof};
+//!
+//! struct AllPositive;
+//!
+//! // Safety: holds is deterministic, and i32 has no interior mutability
+//! unsafe impl Property<i32> for AllPositive {
+//!     fn holds(values: &[i32]) -> bool {
+//!         values.iter().all(|x| *x > 0)
+//!     }
+//! }
+//!
+//! fn sum_positive<Name>(v: &FixedVec<i32, Name>, _proof: Proof<'_, Name, AllPositive>) -> u64 {
+//!     v.iter().map(|x| *x as u64).sum()
+//! }
+//!
+//! let v = vec![1, 2, 3];
+//! let v = name!(v);
+//! let v = FixedVec::fix(v);
+//!
+//! let proof = v.check_property::<AllPositive>().unwrap();
+//!
+//! assert_eq!(sum_positive(&v, proof), 6);
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::FixedVec;
+
+/// A proof that the property ``P`` holds for the value with name ``Name``. The proof is only
+/// guaranteed to hold during the lifetime ``'a``.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""))]
+pub struct Proof<'a, Name, P> {
+    _phantom: PhantomData<(&'a (), Name, P)>,
+}
+
+/// The property that both ``P`` and ``Q`` hold.
+pub struct And<P, Q> {
+    _phantom: PhantomData<(P, Q)>,
+}
+
+/// The property that at least one of ``P`` and ``Q`` hold.
+pub struct Or<P, Q> {
+    _phantom: PhantomData<(P, Q)>,
+}
+
+/// A property of a slice of values that can be checked at runtime. Implement this trait to use
+/// your own property with the ``check_property`` method of a ``FixedVec``.
+///
+/// # Safety
+///
+/// ``check_property`` and ``Proof::elim`` create proofs from the result of ``holds``, and unsafe
+/// code is allowed to rely on a ``Proof`` being true, so ``holds`` must not lie. It must be
+/// deterministic, returning the same result whenever it is called on the same values, and it must
+/// only return ``true`` when the property really holds. The result also can't depend on anything
+/// that can change through a shared reference, like the contents of a ``Cell`` or an atomic,
+/// since a ``Proof`` only borrows the values immutably.
+pub unsafe trait Property<A> {
+    /// Returns whether the property holds for the given values.
+    fn holds(values: &[A]) -> bool;
+}
+
+/// Introduce a new proof without checking anything. This is the escape hatch that every other
+/// proof is eventually built on, and should only be used inside of functions that check the
+/// property themselves.
+///
+/// # Safety
+///
+/// The property ``P`` must actually hold for the value with name ``Name`` during the lifetime
+/// ``'a``, since unsafe code is allowed to rely on a ``Proof`` being true.
+pub unsafe fn axiom<'a, Name, P>() -> Proof<'a, Name, P> {
+    Proof {
+        _phantom: PhantomData,
+    }
+}
+
+impl<'a, Name, P> Proof<'a, Name, P> {
+    /// Combine this proof with a proof of ``Q`` to get a proof that both hold.
+    pub fn and<Q>(self, _other: Proof<'a, Name, Q>) -> Proof<'a, Name, And<P, Q>> {
+        unsafe { axiom() }
+    }
+
+    /// Weaken this proof into a proof that either ``P`` or ``Q`` hold.
+    pub fn or_left<Q>(self) -> Proof<'a, Name, Or<P, Q>> {
+        unsafe { axiom() }
+    }
+
+    /// Weaken this proof into a proof that either ``Q`` or ``P`` hold.
+    pub fn or_right<Q>(self) -> Proof<'a, Name, Or<Q, P>> {
+        unsafe { axiom() }
+    }
+
+    /// Use an implication, written as a function from proofs of ``P`` to proofs of ``Q``, to get
+    /// a proof of ``Q``.
+    pub fn implies<Q, F>(self, implication: F) -> Proof<'a, Name, Q>
+    where
+        F: FnOnce(Self) -> Proof<'a, Name, Q>,
+    {
+        implication(self)
+    }
+}
+
+impl<'a, Name, P, Q> Proof<'a, Name, And<P, Q>> {
+    /// Get the proof of ``P`` out of a proof that both ``P`` and ``Q`` hold.
+    pub fn left(self) -> Proof<'a, Name, P> {
+        unsafe { axiom() }
+    }
+
+    /// Get the proof of ``Q`` out of a proof that both ``P`` and ``Q`` hold.
+    pub fn right(self) -> Proof<'a, Name, Q> {
+        unsafe { axiom() }
+    }
+}
+
+impl<'a, Name, P, Q> Proof<'a, Name, Or<P, Q>> {
+    /// Get a proof of ``R`` out of a proof that either ``P`` or ``Q`` hold, given that both ``P``
+    /// and ``Q`` imply ``R``. Proofs carry no runtime information, so ``P`` is checked against
+    /// ``values`` to decide which implication to call. Only the implication for a property that
+    /// actually holds is called, and its result is returned.
+    ///
+    /// Note that this runs ``P::holds`` again, which usually takes time linear in the length of
+    /// ``values``. ``values`` has to be the ``FixedVec`` with name ``Name`` that the proof talks
+    /// about, so this can't be used with proofs about other kinds of values.
+    pub fn elim<A, R, F, G>(self, values: &FixedVec<A, Name>, if_left: F, if_right: G) -> Proof<'a, Name, R>
+    where
+        P: Property<A>,
+        F: FnOnce(Proof<'a, Name, P>) -> Proof<'a, Name, R>,
+        G: FnOnce(Proof<'a, Name, Q>) -> Proof<'a, Name, R>,
+    {
+        if P::holds(values) {
+            if_left(unsafe { axiom() })
+        } else {
+            // Since P doesn't hold, Q must
+            if_right(unsafe { axiom() })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    struct AllEven;
+
+    unsafe impl Property<u32> for AllEven {
+        fn holds(values: &[u32]) -> bool {
+            values.iter().all(|x| x % 2 == 0)
+        }
+    }
+
+    struct AllSmall;
+
+    unsafe impl Property<u32> for AllSmall {
+        fn holds(values: &[u32]) -> bool {
+            values.iter().all(|x| *x < 100)
+        }
+    }
+
+    #[test]
+    fn combinators() {
+        let v = vec![2, 4, 6];
+        let v = name!(v);
+        let v = FixedVec::fix(v);
+
+        let even = v.check_property::<AllEven>().unwrap();
+        let small = v.check_property::<AllSmall>().unwrap();
+
+        let both = even.and(small);
+        let _: Proof<_, AllSmall> = both.right();
+        let either: Proof<_, Or<AllEven, AllSmall>> = both.left().or_left();
+        let _: Proof<_, AllSmall> = either.elim(&v, |_| small, |p| p);
+
+        let w = vec![1, 2];
+        let w = name!(w);
+        let w = FixedVec::fix(w);
+
+        assert!(w.check_property::<AllEven>().is_none());
+        let small = w.check_property::<AllSmall>().unwrap();
+        let either: Proof<_, Or<AllEven, AllSmall>> = small.or_right();
+        let _: Proof<_, AllSmall> = either.elim(&w, |_| unreachable!(), |p| p);
+    }
+
+    #[test]
+    #[should_panic(expected = "no proof of R")]
+    fn elim_calls_an_implication() {
+        struct Never;
+
+        let v = vec![2, 4, 6];
+        let v = name!(v);
+        let v = FixedVec::fix(v);
+
+        let either: Proof<_, Or<AllEven, AllSmall>> = v.check_property::<AllEven>().unwrap().or_left();
+        let _: Proof<_, Never> = either.elim(&v, |_| panic!("no proof of R"), |_| panic!("no proof of R"));
+    }
+}
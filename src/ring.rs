@@ -0,0 +1,144 @@
+use std::mem;
+
+use crate::{Bounded, FixedArray};
+
+/// A ring buffer that always holds exactly ``N`` elements. Pushing a new element overwrites the
+/// oldest one, and elements are accessed by their ``Bounded<N>`` position relative to the oldest
+/// element, so no access ever needs a bounds check.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let mut delay_line = FixedRing::new([0.0f32; 4]);
+///
+/// // The newest element is at position 3, so this is the element pushed two samples ago
+/// let delay = Bounded::<4>::new(3 - 2).unwrap();
+///
+/// let mut output = vec![];
+/// for sample in [1.0, 2.0, 3.0, 4.0, 5.0] {
+///     delay_line.push(sample);
+///     output.push(delay_line[delay]);
+/// }
+///
+/// assert_eq!(output, vec![0.0, 0.0, 1.0, 2.0, 3.0]);
+/// ```
+///
+/// A ring buffer can't be empty, which is checked at compile time:
+///
+/// ```compile_fail
+/// # use fixed_vec::*;
+/// let ring = FixedRing::<u8, 0>::new([]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FixedRing<A, const N: usize> {
+    buffer: FixedArray<A, N>,
+    head: Bounded<N>,
+}
+
+struct NonZeroLen<const N: usize>;
+
+impl<const N: usize> NonZeroLen<N> {
+    const OK: () = assert!(N > 0, "ring buffer must not be empty");
+}
+
+impl<A, const N: usize> FixedRing<A, N> {
+    /// Create a ``FixedRing`` holding the elements of ``buffer``, with the first element being the
+    /// oldest. ``N`` must not be 0.
+    pub fn new(buffer: [A; N]) -> Self {
+        let () = NonZeroLen::<N>::OK;
+        FixedRing {
+            buffer: FixedArray::new(buffer),
+            head: match Bounded::new(0) {
+                Some(head) => head,
+                None => unreachable!(),
+            },
+        }
+    }
+
+    /// Push a new element, returning the oldest element that it replaces.
+    #[inline(always)]
+    pub fn push(&mut self, value: A) -> A {
+        let oldest = mem::replace(&mut self.buffer[self.head], value);
+        self.head = self.head.next();
+        oldest
+    }
+
+    /// Get an element by its position relative to the oldest element, without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, position: Bounded<N>) -> &A {
+        &self.buffer[self.head.wrapping_add(*position)]
+    }
+
+    /// Get a mutable reference to an element by its position relative to the oldest element,
+    /// without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, position: Bounded<N>) -> &mut A {
+        &mut self.buffer[self.head.wrapping_add(*position)]
+    }
+
+    /// The oldest element, which will be replaced by the next ``push``.
+    pub fn oldest(&self) -> &A {
+        &self.buffer[self.head]
+    }
+
+    /// The most recently pushed element.
+    pub fn newest(&self) -> &A {
+        &self.buffer[self.head.prev()]
+    }
+
+    /// The position in the underlying array of the oldest element.
+    pub fn head(&self) -> Bounded<N> {
+        self.head
+    }
+
+    /// Returns an iterator over every element, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &A> + ExactSizeIterator {
+        Bounded::<N>::all().map(move |position| self.get(position))
+    }
+
+    /// Unwrap the underlying array. Note that the elements are returned in the order they are
+    /// stored in, so the oldest element is at position ``head()`` rather than at 0.
+    pub fn into_inner(self) -> [A; N] {
+        self.buffer.into_inner()
+    }
+}
+
+impl<A, const N: usize> std::ops::Index<Bounded<N>> for FixedRing<A, N> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, position: Bounded<N>) -> &A {
+        self.get(position)
+    }
+}
+
+impl<A, const N: usize> std::ops::IndexMut<Bounded<N>> for FixedRing<A, N> {
+    #[inline(always)]
+    fn index_mut(&mut self, position: Bounded<N>) -> &mut A {
+        self.get_mut(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_order() {
+        let mut ring = FixedRing::new([1, 2, 3]);
+        assert_eq!(ring.oldest(), &1);
+        assert_eq!(ring.newest(), &3);
+
+        assert_eq!(ring.push(4), 1);
+        assert_eq!(ring.push(5), 2);
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(*ring.head(), 2);
+
+        ring[Bounded::new(0).unwrap()] = 10;
+        assert_eq!(ring.oldest(), &10);
+        assert_eq!(ring.newest(), &5);
+        assert_eq!(ring.iter().rev().copied().collect::<Vec<_>>(), vec![5, 4, 10]);
+        assert_eq!(ring.into_inner(), [4, 5, 10]);
+    }
+}
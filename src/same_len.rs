@@ -0,0 +1,411 @@
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use type_name_value::Named;
+
+use crate::{CheckedRange, FixedVec, Index};
+
+/// A proof that the ``FixedVec``'s with names ``N1`` and ``N2`` have the same length. This means
+/// that any ``Index`` into one of them is also valid in the other. Since a ``FixedVec`` can still
+/// grow, the proof borrows both ``FixedVec``'s for the lifetime ``'a`` so that neither can be
+/// pushed to while it is alive.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""))]
+pub struct SameLen<'a, N1, N2> {
+    _phantom: PhantomData<(&'a (), N1, N2)>,
+}
+
+/// Check whether two ``FixedVec``'s have the same length. This is the only way to create a
+/// ``SameLen``.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let a = vec![1, 2, 3];
+/// let a = name!(a);
+/// let a = FixedVec::fix(a);
+///
+/// let b = vec!['a', 'b', 'c'];
+/// let b = name!(b);
+/// let b = FixedVec::fix(b);
+///
+/// let proof = same_len(&a, &b).unwrap();
+///
+/// let index = a.check_index(2).unwrap();
+///
+/// assert_eq!(b.get(proof.cast_index(index)), &'c');
+/// ```
+pub fn same_len<'a, A, B, N1, N2>(a: &'a FixedVec<A, N1>, b: &'a FixedVec<B, N2>) -> Option<SameLen<'a, N1, N2>> {
+    if a.len() == b.len() {
+        Some(SameLen {
+            _phantom: PhantomData,
+        })
+    } else {
+        None
+    }
+}
+
+impl<'a, N1, N2> SameLen<'a, N1, N2> {
+    /// Convert an ``Index`` of the first ``FixedVec`` into an ``Index`` of the second.
+    pub fn cast_index(&self, index: Index<N1>) -> Index<N2> {
+        Index {
+            index: index.index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert a ``CheckedRange`` of the first ``FixedVec`` into a ``CheckedRange`` of the second.
+    pub fn cast_range(&self, range: CheckedRange<N1>) -> CheckedRange<N2> {
+        CheckedRange {
+            range: range.range,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Swap the roles of the two ``FixedVec``'s.
+    pub fn flip(self) -> SameLen<'a, N2, N1> {
+        SameLen {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Two ``Vec``'s of the same length fixed under one shared ``Name``, so that a single ``Index``
+/// is valid in both of them. Elements can only be pushed to both at once, so the lengths stay
+/// equal.
+///
+/// To share one name between any number of ``Vec``'s with the same element type, use
+/// ``FixedVecs`` instead.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = (vec![1, 2, 3], vec![0.5, 1.5, 2.5]);
+/// let v = name!(v);
+/// let mut v = FixedPair::fix(v).unwrap();
+///
+/// let index = v.push(4, 3.5);
+///
+/// let (a, b) = v.get_mut(index);
+/// *a += 1;
+/// *b *= 2.0;
+///
+/// assert_eq!(v.get(index), (&5, &7.0));
+/// ```
+#[derive(Debug)]
+pub struct FixedPair<A, B, Name> {
+    first: Vec<A>,
+    second: Vec<B>,
+    _phantom: PhantomData<Name>,
+}
+
+impl<A, B, Name> FixedPair<A, B, Name> {
+    /// Create a ``FixedPair`` from a named pair of ``Vec``'s. If the ``Vec``'s don't have the same
+    /// length, they are given back unchanged.
+    pub fn fix(val: Named<(Vec<A>, Vec<B>), Name>) -> Result<Self, (Vec<A>, Vec<B>)> {
+        let (first, second) = val.unname();
+        if first.len() == second.len() {
+            Ok(FixedPair {
+                first,
+                second,
+                _phantom: PhantomData,
+            })
+        } else {
+            Err((first, second))
+        }
+    }
+
+    /// Unwrap the inner ``Vec``'s so that they can be changed again, including their lengths.
+    pub fn unfix(self) -> (Vec<A>, Vec<B>) {
+        (self.first, self.second)
+    }
+
+    /// The shared length of both ``Vec``'s.
+    pub fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    /// Returns ``true`` if both ``Vec``'s are empty.
+    pub fn is_empty(&self) -> bool {
+        self.first.is_empty()
+    }
+
+    /// Perform an index bounds check. The created ``Index`` is valid in both ``Vec``'s.
+    pub fn check_index(&self, index: usize) -> Option<Index<Name>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Perform an index bounds check on a whole range of indices.
+    pub fn check_range(&self, range: Range<usize>) -> Option<CheckedRange<Name>> {
+        if range.end > self.len() {
+            None
+        } else {
+            Some(CheckedRange {
+                range,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get the elements of both ``Vec``'s at ``index`` without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Name>) -> (&A, &B) {
+        unsafe {
+            (self.first.get_unchecked(index.index), self.second.get_unchecked(index.index))
+        }
+    }
+
+    /// Get mutable references to the elements of both ``Vec``'s at ``index`` without bounds
+    /// checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: Index<Name>) -> (&mut A, &mut B) {
+        unsafe {
+            (self.first.get_unchecked_mut(index.index), self.second.get_unchecked_mut(index.index))
+        }
+    }
+
+    /// Get both ``Vec``'s as slices.
+    pub fn as_slices(&self) -> (&[A], &[B]) {
+        (&self.first, &self.second)
+    }
+
+    /// Get both ``Vec``'s as mutable slices.
+    pub fn as_mut_slices(&mut self) -> (&mut [A], &mut [B]) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Append an element to the back of both ``Vec``'s, returning the ``Index`` of the new
+    /// elements.
+    pub fn push(&mut self, a: A, b: B) -> Index<Name> {
+        let index = self.len();
+        self.first.push(a);
+        self.second.push(b);
+        Index {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Reserves capacity for at least ``additional`` more elements in both ``Vec``'s.
+    pub fn reserve(&mut self, additional: usize) {
+        self.first.reserve(additional);
+        self.second.reserve(additional);
+    }
+}
+
+/// ``N`` ``Vec``'s of the same length fixed under one shared ``Name``, so that a single ``Index``
+/// is valid in all of them. This is like ``FixedPair``, but for any number of ``Vec``'s with the
+/// same element type. Elements can only be pushed to all of them at once, so the lengths stay
+/// equal.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = [vec![1, 2], vec![3, 4], vec![5, 6]];
+/// let v = name!(v);
+/// let mut v = FixedVecs::fix(v).unwrap();
+///
+/// let index = v.push([7, 8, 9]);
+///
+/// for value in v.get_mut(index) {
+///     *value *= 10;
+/// }
+///
+/// assert_eq!(v.get(index), [&70, &80, &90]);
+/// assert_eq!(v.unfix(), [vec![1, 2, 70], vec![3, 4, 80], vec![5, 6, 90]]);
+/// ```
+#[derive(Debug)]
+pub struct FixedVecs<A, Name, const N: usize> {
+    vecs: [Vec<A>; N],
+    _phantom: PhantomData<Name>,
+}
+
+impl<A, Name, const N: usize> FixedVecs<A, Name, N> {
+    /// Create a ``FixedVecs`` from a named array of ``Vec``'s. If the ``Vec``'s don't all have
+    /// the same length, they are given back unchanged.
+    pub fn fix(val: Named<[Vec<A>; N], Name>) -> Result<Self, [Vec<A>; N]> {
+        let vecs = val.unname();
+        if vecs.iter().all(|v| v.len() == vecs[0].len()) {
+            Ok(FixedVecs {
+                vecs,
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(vecs)
+        }
+    }
+
+    /// Unwrap the inner ``Vec``'s so that they can be changed again, including their lengths.
+    pub fn unfix(self) -> [Vec<A>; N] {
+        self.vecs
+    }
+
+    /// The shared length of every ``Vec``. This is 0 if ``N`` is 0.
+    pub fn len(&self) -> usize {
+        self.vecs.first().map_or(0, Vec::len)
+    }
+
+    /// Returns ``true`` if every ``Vec`` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Perform an index bounds check. The created ``Index`` is valid in every ``Vec``.
+    pub fn check_index(&self, index: usize) -> Option<Index<Name>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Perform an index bounds check on a whole range of indices.
+    pub fn check_range(&self, range: Range<usize>) -> Option<CheckedRange<Name>> {
+        if range.end > self.len() {
+            None
+        } else {
+            Some(CheckedRange {
+                range,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get the elements of every ``Vec`` at ``index`` without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Name>) -> [&A; N] {
+        self.vecs.each_ref().map(|v| unsafe {
+            v.get_unchecked(index.index)
+        })
+    }
+
+    /// Get mutable references to the elements of every ``Vec`` at ``index`` without bounds
+    /// checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: Index<Name>) -> [&mut A; N] {
+        self.vecs.each_mut().map(|v| unsafe {
+            v.get_unchecked_mut(index.index)
+        })
+    }
+
+    /// Get every ``Vec`` as a slice.
+    pub fn as_slices(&self) -> [&[A]; N] {
+        self.vecs.each_ref().map(Vec::as_slice)
+    }
+
+    /// Get every ``Vec`` as a mutable slice.
+    pub fn as_mut_slices(&mut self) -> [&mut [A]; N] {
+        self.vecs.each_mut().map(Vec::as_mut_slice)
+    }
+
+    /// Append one element to the back of each ``Vec``, returning the ``Index`` of the new
+    /// elements.
+    pub fn push(&mut self, values: [A; N]) -> Index<Name> {
+        let index = self.len();
+        for (v, value) in self.vecs.iter_mut().zip(IntoIterator::into_iter(values)) {
+            v.push(value);
+        }
+        Index {
+            index,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Reserves capacity for at least ``additional`` more elements in every ``Vec``.
+    pub fn reserve(&mut self, additional: usize) {
+        for v in &mut self.vecs {
+            v.reserve(additional);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn same_len_cast() {
+        let a = vec![1, 2, 3];
+        let a = name!(a);
+        let a = FixedVec::fix(a);
+
+        let b = vec![4, 5, 6];
+        let b = name!(b);
+        let b = FixedVec::fix(b);
+
+        let c = vec![7];
+        let c = name!(c);
+        let c = FixedVec::fix(c);
+
+        assert!(same_len(&a, &c).is_none());
+
+        let proof = same_len(&a, &b).unwrap();
+        let range = a.check_range(0..3).unwrap();
+
+        let sum: i32 = proof.cast_range(range).map(|i| b.get(i)).sum();
+        assert_eq!(sum, 15);
+
+        let index = b.check_index(0).unwrap();
+        assert_eq!(a.get(proof.flip().cast_index(index)), &1);
+    }
+
+    #[test]
+    fn fixed_pair() {
+        let v = (vec![1, 2], vec![3]);
+        let v = name!(v);
+        assert!(FixedPair::fix(v).is_err());
+
+        let v = (vec![1, 2], vec!["a", "b"]);
+        let v = name!(v);
+        let mut v = FixedPair::fix(v).unwrap();
+
+        let index = v.push(3, "c");
+        assert_eq!(v.get(index), (&3, &"c"));
+        assert_eq!(v.len(), 3);
+
+        for i in v.check_range(0..2).unwrap() {
+            *v.get_mut(i).0 *= 10;
+        }
+
+        assert_eq!(v.unfix(), (vec![10, 20, 3], vec!["a", "b", "c"]));
+    }
+
+    #[test]
+    fn fixed_vecs() {
+        let v = [vec![1, 2], vec![3], vec![4, 5]];
+        let v = name!(v);
+        assert!(FixedVecs::fix(v).is_err());
+
+        let v = [vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]];
+        let v = name!(v);
+        let mut v = FixedVecs::fix(v).unwrap();
+
+        let index = v.push([9, 10, 11, 12]);
+        assert_eq!(v.get(index), [&9, &10, &11, &12]);
+        assert_eq!(v.len(), 3);
+
+        for i in v.check_range(0..2).unwrap() {
+            *v.get_mut(i)[3] *= 10;
+        }
+        assert_eq!(v.as_slices()[3], &[70, 80, 12]);
+
+        let empty: [Vec<u8>; 0] = [];
+        let empty = name!(empty);
+        let empty = FixedVecs::fix(empty).unwrap();
+        assert!(empty.check_index(0).is_none());
+    }
+}
@@ -0,0 +1,356 @@
+use std::iter::{StepBy, Take};
+use std::marker::PhantomData;
+use std::slice;
+
+use crate::{BorrowedMutFixedVec, Brand, CheckedRange, FixedVec, Index};
+
+/// A view of every ``stride``'th element of a ``FixedVec`` with name ``Name``, starting at
+/// ``offset``. The view has its own name ``Sub``, and an ``Index<Sub>`` with value ``i`` refers to
+/// the element at ``offset + i * stride``. The bounds of the whole view are checked once when it is
+/// created, so indexing into it never needs a bounds check.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// // Interleaved stereo samples
+/// let v = vec![1, -1, 2, -2, 3, -3];
+/// let v = name!(v);
+/// let v = FixedVec::fix(v);
+///
+/// let i = v.strided(1, 2, 3, |right| {
+///     let i = right.check_index(2).unwrap();
+///
+///     assert_eq!(right[i], -3);
+///     assert_eq!(right.iter().copied().collect::<Vec<_>>(), vec![-1, -2, -3]);
+///     right.to_parent(i)
+/// }).unwrap();
+///
+/// assert_eq!(*i, 5);
+/// assert!(v.strided(1, 2, 4, |_| ()).is_none());
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound="A: std::fmt::Debug"))]
+pub struct FixedStrided<'a, A, Name, Sub> {
+    inner: &'a [A],
+    offset: usize,
+    stride: usize,
+    count: usize,
+    _phantom: PhantomData<(Name, Sub)>,
+}
+
+/// A mutable strided view into a ``FixedVec``. See ``FixedStrided`` for more information. Unlike
+/// ``FixedStrided``, a ``FixedStridedMut`` with more than one element can't have a stride of 0,
+/// so that each ``Index<Sub>`` refers to a different element.
+#[derive(Derivative)]
+#[derivative(Debug(bound="A: std::fmt::Debug"))]
+pub struct FixedStridedMut<'a, A, Name, Sub> {
+    inner: &'a mut [A],
+    offset: usize,
+    stride: usize,
+    count: usize,
+    _phantom: PhantomData<(Name, Sub)>,
+}
+
+/// Check that ``count`` elements starting at ``offset`` and spaced ``stride`` apart all fit in a
+/// slice of length ``len``. The offset must be in bounds even if ``count`` is 0, so that the rest
+/// of the slice after it can be taken.
+fn strided_in_bounds(len: usize, offset: usize, stride: usize, count: usize) -> bool {
+    match count.checked_sub(1) {
+        None => offset <= len,
+        Some(last) => last
+            .checked_mul(stride)
+            .and_then(|end| end.checked_add(offset))
+            .is_some_and(|end| end < len),
+    }
+}
+
+impl<A, Name> FixedVec<A, Name> {
+    /// Create a ``FixedStrided`` over ``count`` elements, starting at ``offset`` and spaced
+    /// ``stride`` apart, and pass it to ``f``. The view is named with a fresh ``Brand``. Returns
+    /// ``None`` without calling ``f`` if any of the elements would be out of bounds.
+    pub fn strided<'b, R, F>(&'b self, offset: usize, stride: usize, count: usize, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedStrided<'b, A, Name, Brand<'id>>) -> R,
+    {
+        if !strided_in_bounds(self.len(), offset, stride, count) {
+            return None;
+        }
+        Some(f(FixedStrided {
+            inner: self,
+            offset,
+            stride,
+            count,
+            _phantom: PhantomData,
+        }))
+    }
+
+    /// Create a ``FixedStridedMut`` over ``count`` elements, starting at ``offset`` and spaced
+    /// ``stride`` apart, and pass it to ``f``. The view is named with a fresh ``Brand``. Returns
+    /// ``None`` without calling ``f`` if any of the elements would be out of bounds, or if
+    /// ``stride`` is 0 and ``count`` is more than 1.
+    pub fn strided_mut<'b, R, F>(&'b mut self, offset: usize, stride: usize, count: usize, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedStridedMut<'b, A, Name, Brand<'id>>) -> R,
+    {
+        if !strided_in_bounds(self.len(), offset, stride, count) || (stride == 0 && count > 1) {
+            return None;
+        }
+        Some(f(FixedStridedMut {
+            inner: self.as_mut_slice(),
+            offset,
+            stride,
+            count,
+            _phantom: PhantomData,
+        }))
+    }
+}
+
+impl<'a, A, Name> BorrowedMutFixedVec<'a, A, Name> {
+    /// Create a ``FixedStrided`` over ``count`` elements, starting at ``offset`` and spaced
+    /// ``stride`` apart, and pass it to ``f``. The view is named with a fresh ``Brand``. Returns
+    /// ``None`` without calling ``f`` if any of the elements would be out of bounds.
+    pub fn strided<'b, R, F>(&'b self, offset: usize, stride: usize, count: usize, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedStrided<'b, A, Name, Brand<'id>>) -> R,
+    {
+        if !strided_in_bounds(self.len(), offset, stride, count) {
+            return None;
+        }
+        Some(f(FixedStrided {
+            inner: self,
+            offset,
+            stride,
+            count,
+            _phantom: PhantomData,
+        }))
+    }
+
+    /// Create a ``FixedStridedMut`` over ``count`` elements, starting at ``offset`` and spaced
+    /// ``stride`` apart, and pass it to ``f``. The view is named with a fresh ``Brand``. Returns
+    /// ``None`` without calling ``f`` if any of the elements would be out of bounds, or if
+    /// ``stride`` is 0 and ``count`` is more than 1.
+    pub fn strided_mut<'b, R, F>(&'b mut self, offset: usize, stride: usize, count: usize, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedStridedMut<'b, A, Name, Brand<'id>>) -> R,
+    {
+        if !strided_in_bounds(self.len(), offset, stride, count) || (stride == 0 && count > 1) {
+            return None;
+        }
+        Some(f(FixedStridedMut {
+            inner: self.as_mut_slice(),
+            offset,
+            stride,
+            count,
+            _phantom: PhantomData,
+        }))
+    }
+}
+
+impl<'a, A, Name, Sub> FixedStrided<'a, A, Name, Sub> {
+    /// The number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns ``true`` if the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The position of the view's first element in the parent ``FixedVec``.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The distance between consecutive elements of the view in the parent ``FixedVec``.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Perform an index bounds check relative to the start of the view.
+    pub fn check_index(&self, index: usize) -> Option<Index<Sub>> {
+        if self.count <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns a ``CheckedRange`` over every index of the view.
+    pub fn indices(&self) -> CheckedRange<Sub> {
+        CheckedRange {
+            range: 0..self.count,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert an index of the view into an index of the parent ``FixedVec``.
+    #[inline(always)]
+    pub fn to_parent(&self, index: Index<Sub>) -> Index<Name> {
+        Index {
+            index: self.offset + index.index * self.stride,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get an element of the view without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Sub>) -> &'a A {
+        unsafe {
+            self.inner.get_unchecked(self.offset + index.index * self.stride)
+        }
+    }
+
+    /// Returns an iterator over the elements of the view, in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'a A> + ExactSizeIterator {
+        let view = *self;
+        self.indices().map(move |index| view.get(index))
+    }
+}
+
+impl<'a, A, Name, Sub> FixedStridedMut<'a, A, Name, Sub> {
+    /// The number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns ``true`` if the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The position of the view's first element in the parent ``FixedVec``.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The distance between consecutive elements of the view in the parent ``FixedVec``.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Perform an index bounds check relative to the start of the view.
+    pub fn check_index(&self, index: usize) -> Option<Index<Sub>> {
+        if self.count <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns a ``CheckedRange`` over every index of the view.
+    pub fn indices(&self) -> CheckedRange<Sub> {
+        CheckedRange {
+            range: 0..self.count,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert an index of the view into an index of the parent ``FixedVec``.
+    #[inline(always)]
+    pub fn to_parent(&self, index: Index<Sub>) -> Index<Name> {
+        Index {
+            index: self.offset + index.index * self.stride,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get an element of the view without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Sub>) -> &A {
+        unsafe {
+            self.inner.get_unchecked(self.offset + index.index * self.stride)
+        }
+    }
+
+    /// Get a mutable reference to an element of the view without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: Index<Sub>) -> &mut A {
+        unsafe {
+            self.inner.get_unchecked_mut(self.offset + index.index * self.stride)
+        }
+    }
+
+    /// Returns an iterator over the elements of the view, in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &A> + ExactSizeIterator {
+        self.indices().map(move |index| self.get(index))
+    }
+
+    /// Returns an iterator over mutable references to the elements of the view, in order.
+    pub fn iter_mut(&mut self) -> Take<StepBy<slice::IterMut<'_, A>>> {
+        // A stride of 0 is only allowed with at most one element, so it can be treated as 1
+        let stride = self.stride.max(1);
+        unsafe {
+            self.inner.get_unchecked_mut(self.offset..).iter_mut().step_by(stride).take(self.count)
+        }
+    }
+}
+
+impl<'a, A, Name, Sub> std::ops::Index<Index<Sub>> for FixedStrided<'a, A, Name, Sub> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: Index<Sub>) -> &A {
+        self.get(index)
+    }
+}
+
+impl<'a, A, Name, Sub> std::ops::Index<Index<Sub>> for FixedStridedMut<'a, A, Name, Sub> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: Index<Sub>) -> &A {
+        self.get(index)
+    }
+}
+
+impl<'a, A, Name, Sub> std::ops::IndexMut<Index<Sub>> for FixedStridedMut<'a, A, Name, Sub> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: Index<Sub>) -> &mut A {
+        self.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn bounds() {
+        assert!(strided_in_bounds(0, 0, 5, 0));
+        assert!(!strided_in_bounds(0, 1, 5, 0));
+        assert!(strided_in_bounds(10, 9, 0, 100));
+        assert!(strided_in_bounds(10, 1, 4, 3));
+        assert!(!strided_in_bounds(10, 2, 4, 3));
+        assert!(!strided_in_bounds(10, 1, usize::MAX, 2));
+        assert!(!strided_in_bounds(10, usize::MAX, 1, 2));
+    }
+
+    #[test]
+    fn rgba_channels() {
+        let v = vec![10u8, 20, 30, 255, 40, 50, 60, 255];
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        assert!(v.strided_mut(0, 0, 2, |_| ()).is_none());
+        v.strided_mut(1, 4, 2, |mut green| {
+            for value in green.iter_mut() {
+                *value /= 2;
+            }
+            let i = green.check_index(1).unwrap();
+            green[i] += 1;
+            assert!(green.check_index(2).is_none());
+        }).unwrap();
+
+        assert_eq!(v.strided(3, 4, 2, |alpha| alpha.iter().all(|&a| a == 255)), Some(true));
+        assert_eq!(v.unfix(), vec![10, 10, 30, 255, 40, 26, 60, 255]);
+    }
+}
@@ -0,0 +1,496 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ops::Range;
+
+use crate::{BorrowedMutFixedVec, Brand, CheckedRange, FixedVec, Index};
+
+/// A view into part of a ``FixedVec`` (or another ``FixedSubSlice``) with name ``Name``. The view
+/// has its own name ``Sub``, and an ``Index<Sub>`` is relative to the start of the view. Indices
+/// can be converted back into ``Index<Name>``'s with ``to_parent``.
+///
+/// Views are only handed out to a closure, named with a fresh ``Brand``, so that two views never
+/// share a name even when they are created by the same line of code.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v = vec![1, 2, 3, 4, 5];
+/// let v = name!(v);
+/// let v = FixedVec::fix(v);
+///
+/// let range = v.check_range(2..5).unwrap();
+/// let index = v.sub_slice(range, |sub| {
+///     let sub_index = sub.check_index(0).unwrap();
+///     assert_eq!(sub.get(sub_index), &3);
+///     sub.to_parent(sub_index)
+/// });
+///
+/// assert_eq!(*index, 2);
+/// assert_eq!(v.get(index), &3);
+/// ```
+///
+/// An ``Index`` of one view can't be used with another view, even of the same range:
+///
+/// ```compile_fail
+/// # use fixed_vec::*;
+/// let v = vec![1, 2, 3, 4, 5];
+/// let v = name!(v);
+/// let v = FixedVec::fix(v);
+///
+/// v.sub_slice(v.check_range(0..2).unwrap(), |a| {
+///     v.sub_slice(v.check_range(3..4).unwrap(), |b| {
+///         let index = a.check_index(1).unwrap();
+///         println!("{}", b.get(index));
+///     })
+/// });
+/// ```
+pub struct FixedSubSlice<'a, A, Name, Sub> {
+    pub(crate) inner: &'a [A],
+    pub(crate) offset: usize,
+    pub(crate) _phantom: PhantomData<(Name, Sub)>,
+}
+
+/// A mutable view into part of a ``FixedVec`` (or another ``FixedSubSliceMut``). See
+/// ``FixedSubSlice`` for more information.
+pub struct FixedSubSliceMut<'a, A, Name, Sub> {
+    pub(crate) inner: &'a mut [A],
+    pub(crate) offset: usize,
+    pub(crate) _phantom: PhantomData<(Name, Sub)>,
+}
+
+/// The bounds of the slice described by ``range``. ``CheckedRange`` only guarantees that the end
+/// is in bounds, so a range with a start past its end is treated as empty.
+fn slice_bounds<Name>(range: &CheckedRange<Name>) -> Range<usize> {
+    range.range.start.min(range.range.end)..range.range.end
+}
+
+/// Split ``slice`` at ``mid`` into two mutable sub-slices. ``mid`` must be at most the length of
+/// ``slice``.
+unsafe fn split_slice_mut<A, Name, Left, Right>(slice: &mut [A], mid: usize) -> (FixedSubSliceMut<'_, A, Name, Left>, FixedSubSliceMut<'_, A, Name, Right>) {
+    let (left, right) = slice.split_at_mut_unchecked(mid);
+    (
+        FixedSubSliceMut {
+            inner: left,
+            offset: 0,
+            _phantom: PhantomData,
+        },
+        FixedSubSliceMut {
+            inner: right,
+            offset: mid,
+            _phantom: PhantomData,
+        },
+    )
+}
+
+impl<A, Name> FixedVec<A, Name> {
+    /// Create a ``FixedSubSlice`` over the elements in ``range`` and pass it to ``f``. The
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice<'b, R, F>(&'b self, range: CheckedRange<Name>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSlice<'b, A, Name, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSlice {
+            inner: unsafe { self.get_unchecked(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Create a ``FixedSubSliceMut`` over the elements in ``range`` and pass it to ``f``. The
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice_mut<'b, R, F>(&'b mut self, range: CheckedRange<Name>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSliceMut<'b, A, Name, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSliceMut {
+            inner: unsafe { self.as_mut_slice().get_unchecked_mut(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Split the ``FixedVec`` into two mutable sub-slices and pass them to ``f``, the first
+    /// containing the elements before ``mid`` and the second containing ``mid`` and everything
+    /// after it. Each sub-slice is named with its own fresh ``Brand``.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fixed_vec::*;
+    /// let v = vec![1, 2, 3, 4];
+    /// let v = name!(v);
+    /// let mut v = FixedVec::fix(v);
+    ///
+    /// let mid = v.check_index(2).unwrap();
+    /// v.split_at_mut(mid, |mut left, mut right| {
+    ///     for (i, j) in left.check_range(0..2).unwrap().zip(right.check_range(0..2).unwrap()) {
+    ///         std::mem::swap(left.get_mut(i), right.get_mut(j));
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(v.unfix(), vec![3, 4, 1, 2]);
+    /// ```
+    pub fn split_at_mut<'b, R, F>(&'b mut self, mid: Index<Name>, f: F) -> R
+    where
+        F: for<'l, 'r> FnOnce(FixedSubSliceMut<'b, A, Name, Brand<'l>>, FixedSubSliceMut<'b, A, Name, Brand<'r>>) -> R,
+    {
+        let (left, right) = unsafe {
+            split_slice_mut(self.as_mut_slice(), mid.index)
+        };
+        f(left, right)
+    }
+}
+
+impl<'a, A, Name> BorrowedMutFixedVec<'a, A, Name> {
+    /// Create a ``FixedSubSlice`` over the elements in ``range`` and pass it to ``f``. The
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice<'b, R, F>(&'b self, range: CheckedRange<Name>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSlice<'b, A, Name, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSlice {
+            inner: unsafe { self.get_unchecked(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Create a ``FixedSubSliceMut`` over the elements in ``range`` and pass it to ``f``. The
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice_mut<'b, R, F>(&'b mut self, range: CheckedRange<Name>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSliceMut<'b, A, Name, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSliceMut {
+            inner: unsafe { self.as_mut_slice().get_unchecked_mut(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Split the ``BorrowedMutFixedVec`` into two mutable sub-slices and pass them to ``f``, the
+    /// first containing the elements before ``mid`` and the second containing ``mid`` and
+    /// everything after it. Each sub-slice is named with its own fresh ``Brand``.
+    pub fn split_at_mut<'b, R, F>(&'b mut self, mid: Index<Name>, f: F) -> R
+    where
+        F: for<'l, 'r> FnOnce(FixedSubSliceMut<'b, A, Name, Brand<'l>>, FixedSubSliceMut<'b, A, Name, Brand<'r>>) -> R,
+    {
+        let (left, right) = unsafe {
+            split_slice_mut(self.as_mut_slice(), mid.index)
+        };
+        f(left, right)
+    }
+}
+
+impl<'a, A, Name, Sub> Deref for FixedSubSlice<'a, A, Name, Sub> {
+    type Target = [A];
+
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+impl<'a, A, Name, Sub> Deref for FixedSubSliceMut<'a, A, Name, Sub> {
+    type Target = [A];
+
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+impl<'a, A, Name, Sub> FixedSubSlice<'a, A, Name, Sub> {
+    /// The position of the start of this sub-slice in its parent.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Perform an index bounds check, relative to the start of the sub-slice.
+    pub fn check_index(&self, index: usize) -> Option<Index<Sub>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Perform an index bounds check on a whole range of indices, relative to the start of the
+    /// sub-slice.
+    pub fn check_range(&self, range: Range<usize>) -> Option<CheckedRange<Sub>> {
+        if range.end > self.len() {
+            None
+        } else {
+            Some(CheckedRange {
+                range,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get an element of the sub-slice without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Sub>) -> &'a A {
+        unsafe {
+            self.inner.get_unchecked(index.index)
+        }
+    }
+
+    /// Convert an ``Index`` of this sub-slice into an ``Index`` of its parent.
+    pub fn to_parent(&self, index: Index<Sub>) -> Index<Name> {
+        Index {
+            index: index.index + self.offset,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert a ``CheckedRange`` of this sub-slice into a ``CheckedRange`` of its parent.
+    pub fn range_to_parent(&self, range: CheckedRange<Sub>) -> CheckedRange<Name> {
+        let bounds = slice_bounds(&range);
+        CheckedRange {
+            range: bounds.start + self.offset..bounds.end + self.offset,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert an ``Index`` of the parent into an ``Index`` of this sub-slice, if it lies within
+    /// the sub-slice.
+    pub fn from_parent(&self, index: Index<Name>) -> Option<Index<Sub>> {
+        self.check_index(index.index.checked_sub(self.offset)?)
+    }
+
+    /// Create a ``FixedSubSlice`` over part of this sub-slice and pass it to ``f``. The new
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice<R, F>(&self, range: CheckedRange<Sub>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSlice<'a, A, Sub, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSlice {
+            inner: unsafe { self.inner.get_unchecked(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<'a, A, Name, Sub> FixedSubSliceMut<'a, A, Name, Sub> {
+    /// The position of the start of this sub-slice in its parent.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Perform an index bounds check, relative to the start of the sub-slice.
+    pub fn check_index(&self, index: usize) -> Option<Index<Sub>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Perform an index bounds check on a whole range of indices, relative to the start of the
+    /// sub-slice.
+    pub fn check_range(&self, range: Range<usize>) -> Option<CheckedRange<Sub>> {
+        if range.end > self.len() {
+            None
+        } else {
+            Some(CheckedRange {
+                range,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Get an element of the sub-slice without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: Index<Sub>) -> &A {
+        unsafe {
+            self.inner.get_unchecked(index.index)
+        }
+    }
+
+    /// Get a mutable reference to an element of the sub-slice without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: Index<Sub>) -> &mut A {
+        unsafe {
+            self.inner.get_unchecked_mut(index.index)
+        }
+    }
+
+    /// Swap two elements of the sub-slice without bounds checking.
+    pub fn swap(&mut self, a: Index<Sub>, b: Index<Sub>) {
+        let ptr = self.inner.as_mut_ptr();
+        unsafe {
+            std::ptr::swap(ptr.add(a.index), ptr.add(b.index));
+        }
+    }
+
+    /// Extracts a mutable slice of the entire sub-slice.
+    pub fn as_mut_slice(&mut self) -> &mut [A] {
+        self.inner
+    }
+
+    /// Convert an ``Index`` of this sub-slice into an ``Index`` of its parent.
+    pub fn to_parent(&self, index: Index<Sub>) -> Index<Name> {
+        Index {
+            index: index.index + self.offset,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert a ``CheckedRange`` of this sub-slice into a ``CheckedRange`` of its parent.
+    pub fn range_to_parent(&self, range: CheckedRange<Sub>) -> CheckedRange<Name> {
+        let bounds = slice_bounds(&range);
+        CheckedRange {
+            range: bounds.start + self.offset..bounds.end + self.offset,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Convert an ``Index`` of the parent into an ``Index`` of this sub-slice, if it lies within
+    /// the sub-slice.
+    pub fn from_parent(&self, index: Index<Name>) -> Option<Index<Sub>> {
+        self.check_index(index.index.checked_sub(self.offset)?)
+    }
+
+    /// Create a ``FixedSubSlice`` over part of this sub-slice and pass it to ``f``. The new
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice<'b, R, F>(&'b self, range: CheckedRange<Sub>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSlice<'b, A, Sub, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSlice {
+            inner: unsafe { self.inner.get_unchecked(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Create a ``FixedSubSliceMut`` over part of this sub-slice and pass it to ``f``. The new
+    /// sub-slice is named with a fresh ``Brand``.
+    pub fn sub_slice_mut<'b, R, F>(&'b mut self, range: CheckedRange<Sub>, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedSubSliceMut<'b, A, Sub, Brand<'id>>) -> R,
+    {
+        let bounds = slice_bounds(&range);
+        f(FixedSubSliceMut {
+            inner: unsafe { self.inner.get_unchecked_mut(bounds.clone()) },
+            offset: bounds.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Split the sub-slice into two mutable sub-slices and pass them to ``f``, the first
+    /// containing the elements before ``mid`` and the second containing ``mid`` and everything
+    /// after it. Each sub-slice is named with its own fresh ``Brand``.
+    pub fn split_at_mut<'b, R, F>(&'b mut self, mid: Index<Sub>, f: F) -> R
+    where
+        F: for<'l, 'r> FnOnce(FixedSubSliceMut<'b, A, Sub, Brand<'l>>, FixedSubSliceMut<'b, A, Sub, Brand<'r>>) -> R,
+    {
+        let (left, right) = unsafe {
+            split_slice_mut(self.inner, mid.index)
+        };
+        f(left, right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn sub_slice_conversions() {
+        let v = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let v = name!(v);
+        let v = FixedVec::fix(v);
+
+        v.sub_slice(v.check_range(2..6).unwrap(), |sub| {
+            assert_eq!(&*sub, &[2, 3, 4, 5]);
+            assert!(sub.check_index(4).is_none());
+
+            sub.sub_slice(sub.check_range(1..3).unwrap(), |sub2| {
+                let index = sub2.check_index(1).unwrap();
+                assert_eq!(sub2.get(index), &4);
+                assert_eq!(*sub.to_parent(sub2.to_parent(index)), 4);
+            });
+
+            assert!(sub.from_parent(v.check_index(1).unwrap()).is_none());
+            assert!(sub.from_parent(v.check_index(6).unwrap()).is_none());
+            let index = sub.from_parent(v.check_index(5).unwrap()).unwrap();
+            assert_eq!(sub.get(index), &5);
+
+            let range = sub.range_to_parent(sub.check_range(0..2).unwrap());
+            assert_eq!(range.map(|i| *v.get(i)).collect::<Vec<_>>(), vec![2, 3]);
+        });
+    }
+
+    #[test]
+    fn sub_slice_mut_partition() {
+        let v = vec![9, 5, 1, 8, 2, 7];
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        let range = v.check_range(1..6).unwrap();
+        let pivot_index = v.sub_slice_mut(range, |mut sub| {
+            // Lomuto partition around the last element of the sub-slice
+            let pivot_index = sub.check_index(sub.len() - 1).unwrap();
+            let pivot = *sub.get(pivot_index);
+            let mut store = 0;
+            for i in sub.check_range(0..sub.len() - 1).unwrap() {
+                if *sub.get(i) < pivot {
+                    let target = sub.check_index(store).unwrap();
+                    sub.swap(i, target);
+                    store += 1;
+                }
+            }
+            let target = sub.check_index(store).unwrap();
+            sub.swap(pivot_index, target);
+            sub.to_parent(target)
+        });
+
+        assert_eq!(*pivot_index, 4);
+        assert_eq!(v.get(pivot_index), &7);
+        assert_eq!(v.unfix(), vec![9, 5, 1, 2, 7, 8]);
+    }
+
+    #[test]
+    fn split_at_mut_merge() {
+        let v = vec![1, 4, 6, 2, 3, 5];
+        let v = name!(v);
+        let mut v = FixedVec::fix(v);
+
+        let mid = v.check_index(3).unwrap();
+        let merged = v.split_at_mut(mid, |left, right| {
+            assert_eq!(right.offset(), 3);
+
+            let mut merged = vec![];
+            let mut i = left.check_range(0..left.len()).unwrap().peekable();
+            let mut j = right.check_range(0..right.len()).unwrap().peekable();
+            while let (Some(a), Some(b)) = (i.peek(), j.peek()) {
+                if left.get(*a) <= right.get(*b) {
+                    merged.push(left.to_parent(i.next().unwrap()));
+                } else {
+                    merged.push(right.to_parent(j.next().unwrap()));
+                }
+            }
+            merged.extend(i.map(|a| left.to_parent(a)));
+            merged.extend(j.map(|b| right.to_parent(b)));
+            merged
+        });
+
+        let merged: Vec<_> = merged.into_iter().map(|index| *v.get(index)).collect();
+        assert_eq!(merged, vec![1, 2, 3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,339 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::{CheckedRange, Index};
+
+/// A row of a table with name ``Row``, created with ``fixed_table!``. This is the same type as
+/// ``Index<Row>``.
+pub type RowId<Row> = Index<Row>;
+
+/// One column of a table created with ``fixed_table!``. Every column of a table has the same
+/// length and the same name ``Row``, and columns can only grow through the table's ``push_row``
+/// method, so a ``RowId`` from any of them is valid in all of them.
+#[derive(Derivative)]
+#[derivative(Debug(bound="T: std::fmt::Debug"))]
+pub struct Column<T, Row> {
+    values: Vec<T>,
+    _phantom: PhantomData<Row>,
+}
+
+impl<T, Row> Deref for Column<T, Row> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<T, Row> Column<T, Row> {
+    /// Create an empty column. Used by ``fixed_table!``.
+    ///
+    /// # Safety
+    ///
+    /// ``Row`` must be unique, and only used for columns of a single table.
+    #[doc(hidden)]
+    pub unsafe fn new() -> Self {
+        Column {
+            values: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create a column holding ``values``. Used by ``fixed_table!``.
+    ///
+    /// # Safety
+    ///
+    /// ``Row`` must be unique, and only used for columns of a single table, which must all have
+    /// the same length.
+    #[doc(hidden)]
+    pub unsafe fn from_vec(values: Vec<T>) -> Self {
+        Column {
+            values,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Append a value to the column. Used by ``fixed_table!``.
+    ///
+    /// # Safety
+    ///
+    /// Every other column of the same table must be pushed to as well, before any of them are
+    /// indexed again.
+    #[doc(hidden)]
+    pub unsafe fn push(&mut self, value: T) {
+        self.values.push(value);
+    }
+
+    /// Perform a bounds check on a row number.
+    pub fn check_row(&self, index: usize) -> Option<RowId<Row>> {
+        if self.len() <= index {
+            None
+        } else {
+            Some(Index {
+                index,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns an iterator over every row.
+    pub fn rows(&self) -> CheckedRange<Row> {
+        CheckedRange {
+            range: 0..self.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get a value of the column without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, row: RowId<Row>) -> &T {
+        unsafe {
+            self.values.get_unchecked(row.index)
+        }
+    }
+
+    /// Get a mutable reference to a value of the column without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, row: RowId<Row>) -> &mut T {
+        unsafe {
+            self.values.get_unchecked_mut(row.index)
+        }
+    }
+
+    /// Get the whole column as a mutable slice. The slice can't change the length of the column.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Unwrap the values of the column. Since this takes ownership of the column, the table it
+    /// came from can no longer be used.
+    pub fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T, Row> std::ops::Index<RowId<Row>> for Column<T, Row> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, row: RowId<Row>) -> &T {
+        self.get(row)
+    }
+}
+
+impl<T, Row> std::ops::IndexMut<RowId<Row>> for Column<T, Row> {
+    #[inline(always)]
+    fn index_mut(&mut self, row: RowId<Row>) -> &mut T {
+        self.get_mut(row)
+    }
+}
+
+/// Define a record struct along with a struct-of-arrays table of records, with one ``Column`` per
+/// field. Every column of a table shares the table's name ``Row``, so a ``RowId`` from
+/// ``push_row`` or ``check_row`` indexes every column without bounds checking. Columns are fields
+/// of the table with the same visibility as the record's fields, so different columns can be
+/// borrowed at the same time.
+///
+/// The generated table has the methods ``new``, ``fix``, ``len``, ``is_empty``, ``check_row``,
+/// ``rows``, ``push_row`` and ``into_rows``. An empty table is created with ``new`` from a
+/// ``Brand`` token, and a table of existing columns with ``fix`` from a named tuple of ``Vec``'s
+/// of the same length, in field order.
+///
+/// This is a ``macro_rules!`` macro rather than a derive on purpose. A derive would need a
+/// separate proc-macro crate, while this keeps ``fixed_vec`` a single crate with no extra build
+/// dependencies.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// fixed_table! {
+///     #[derive(Debug, PartialEq)]
+///     pub struct Particle {
+///         pub pos: f32,
+///         pub vel: f32,
+///     }
+///     pub table Particles;
+/// }
+///
+/// let rows = Brand::with(|brand| {
+///     let mut particles = Particles::new(brand);
+///     let a = particles.push_row(Particle { pos: 0.0, vel: 1.0 });
+///     particles.push_row(Particle { pos: 5.0, vel: -2.0 });
+///
+///     for row in particles.rows() {
+///         particles.pos[row] += particles.vel[row];
+///     }
+///
+///     assert_eq!(particles.pos[a], 1.0);
+///     particles.into_rows()
+/// });
+///
+/// assert_eq!(rows[1], Particle { pos: 3.0, vel: -2.0 });
+///
+/// let columns = (vec![0.0, 1.0], vec![2.0, 3.0]);
+/// let columns = name!(columns);
+/// let particles = Particles::fix(columns).unwrap();
+/// assert_eq!(particles.vel[particles.check_row(1).unwrap()], 3.0);
+/// ```
+#[macro_export]
+macro_rules! fixed_table {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $record:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty),+ $(,)?
+        }
+        $table_vis:vis table $table:ident;
+    ) => {
+        $(#[$meta])*
+        $vis struct $record {
+            $($(#[$field_meta])* $field_vis $field: $ty),+
+        }
+
+        #[doc = ::core::concat!("A table of ``", ::core::stringify!($record), "``'s stored as one ``Column`` per field.")]
+        $table_vis struct $table<__FixedTableRow> {
+            $($field_vis $field: $crate::Column<$ty, __FixedTableRow>),+
+        }
+
+        impl<'id> $table<$crate::Brand<'id>> {
+            /// Create an empty table, named by a ``Brand`` token from ``Brand::with``.
+            pub fn new(_brand: $crate::Brand<'id>) -> Self {
+                $table {
+                    $($field: unsafe {
+                        // Brand<'id> is unique, since its only token is used up here
+                        $crate::Column::new()
+                    }),+
+                }
+            }
+        }
+
+        impl<__FixedTableRow> $table<__FixedTableRow> {
+            /// Create a table from a named tuple of columns, in field order. If the columns don't
+            /// all have the same length, they are given back unchanged.
+            pub fn fix(
+                columns: $crate::Named<($(::std::vec::Vec<$ty>,)+), __FixedTableRow>,
+            ) -> ::core::result::Result<Self, ($(::std::vec::Vec<$ty>,)+)> {
+                let ($($field,)+) = columns.unname();
+                let len = $crate::fixed_table!(@first_column ($($field),+)).len();
+                if [$($field.len()),+].iter().all(|&field_len| field_len == len) {
+                    ::core::result::Result::Ok($table {
+                        $($field: unsafe {
+                            // The name is unique, since it came from a Named
+                            $crate::Column::from_vec($field)
+                        }),+
+                    })
+                } else {
+                    ::core::result::Result::Err(($($field,)+))
+                }
+            }
+
+            /// The number of rows.
+            pub fn len(&self) -> usize {
+                $crate::fixed_table!(@first_column ($(self.$field),+)).len()
+            }
+
+            /// Returns ``true`` if the table has no rows.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Perform a bounds check on a row number.
+            pub fn check_row(&self, index: usize) -> ::core::option::Option<$crate::RowId<__FixedTableRow>> {
+                $crate::fixed_table!(@first_column ($(self.$field),+)).check_row(index)
+            }
+
+            /// Returns an iterator over every row.
+            pub fn rows(&self) -> $crate::CheckedRange<__FixedTableRow> {
+                $crate::fixed_table!(@first_column ($(self.$field),+)).rows()
+            }
+
+            /// Appends a row to every column, returning its ``RowId``.
+            pub fn push_row(&mut self, row: $record) -> $crate::RowId<__FixedTableRow> {
+                let index = self.len();
+                $(unsafe {
+                    // Every column is pushed to
+                    self.$field.push(row.$field);
+                })+
+                match self.check_row(index) {
+                    ::core::option::Option::Some(id) => id,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+
+            /// Unwrap the table back into records.
+            pub fn into_rows(self) -> ::std::vec::Vec<$record> {
+                let len = self.len();
+                $(let mut $field = ::core::iter::IntoIterator::into_iter(self.$field.into_vec());)+
+                ::core::iter::Iterator::collect((0..len).map(|_| $record {
+                    $($field: ::core::option::Option::unwrap(::core::iter::Iterator::next(&mut $field))),+
+                }))
+            }
+        }
+
+        impl<__FixedTableRow> ::core::fmt::Debug for $table<__FixedTableRow>
+        where
+            $($crate::Column<$ty, __FixedTableRow>: ::core::fmt::Debug),+
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(::core::stringify!($table))
+                    $(.field(::core::stringify!($field), &self.$field))+
+                    .finish()
+            }
+        }
+    };
+    (@first_column ($first:expr $(, $rest:expr)*)) => {
+        $first
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Brand;
+    use type_name_value::name;
+
+    fixed_table! {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Body {
+            name: String,
+            mass: u32,
+            children: Vec<usize>,
+        }
+        table Bodies;
+    }
+
+    #[test]
+    fn columns_share_rows() {
+        let rows = Brand::with(|brand| {
+            let mut bodies = Bodies::new(brand);
+            assert!(bodies.is_empty());
+            assert!(bodies.check_row(0).is_none());
+
+            let sun = bodies.push_row(Body { name: "sun".into(), mass: 1000, children: vec![] });
+            let earth = bodies.push_row(Body { name: "earth".into(), mass: 3, children: vec![] });
+            bodies.children[sun].push(*earth);
+
+            let total: u32 = bodies.rows().map(|row| bodies.mass[row]).sum();
+            assert_eq!(total, 1003);
+            assert_eq!(bodies.check_row(1), Some(earth));
+            assert_eq!(&bodies.name[earth], "earth");
+            assert!(format!("{:?}", bodies).starts_with("Bodies { name: Column"));
+            bodies.into_rows()
+        });
+        assert_eq!(rows[0], Body { name: "sun".into(), mass: 1000, children: vec![1] });
+    }
+
+    #[test]
+    fn fix_columns() {
+        let columns = (vec!["a".to_string()], vec![1, 2], vec![vec![], vec![]]);
+        let columns = name!(columns);
+        assert!(Bodies::fix(columns).is_err());
+
+        let columns = (vec!["a".to_string(), "b".to_string()], vec![1, 2], vec![vec![], vec![0]]);
+        let columns = name!(columns);
+        let mut bodies = Bodies::fix(columns).unwrap();
+        let c = bodies.push_row(Body { name: "c".into(), mass: 3, children: vec![] });
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(bodies.mass[c], 3);
+        assert_eq!(bodies.into_rows()[1], Body { name: "b".into(), mass: 2, children: vec![0] });
+    }
+}
@@ -0,0 +1,489 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, Range};
+
+use type_name_value::Named;
+
+use crate::Brand;
+
+/// A valid position along axis ``K`` of a tensor with name ``Name``. This cannot be created except
+/// through the ``check_axis`` method of a tensor with the same name.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""), PartialOrd(bound=""), Ord(bound=""))]
+pub struct AxisIdx<Name, const K: usize> {
+    index: usize,
+    _phantom: PhantomData<Name>,
+}
+
+impl<Name, const K: usize> Deref for AxisIdx<Name, K> {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+/// A valid position in every axis of a ``D`` dimensional tensor with name ``Name``. A ``MultiIdx``
+/// can be created with ``check_index``, or from a tuple of one ``AxisIdx`` per axis for up to 4
+/// dimensions.
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound=""), PartialEq(bound=""), Eq(bound=""), Hash(bound=""), PartialOrd(bound=""), Ord(bound=""))]
+pub struct MultiIdx<Name, const D: usize> {
+    index: [usize; D],
+    _phantom: PhantomData<Name>,
+}
+
+impl<Name, const D: usize> Deref for MultiIdx<Name, D> {
+    type Target = [usize; D];
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+/// Fails to compile when ``K`` isn't an axis of a ``D`` dimensional tensor.
+struct AxisInBounds<const K: usize, const D: usize>;
+
+impl<const K: usize, const D: usize> AxisInBounds<K, D> {
+    const OK: () = assert!(K < D, "axis out of bounds");
+}
+
+impl<Name, const D: usize> MultiIdx<Name, D> {
+    /// The position along axis ``K``.
+    pub fn axis<const K: usize>(&self) -> AxisIdx<Name, K> {
+        let () = AxisInBounds::<K, D>::OK;
+        AxisIdx {
+            index: self.index[K],
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Replace the position along axis ``K``.
+    pub fn with_axis<const K: usize>(mut self, index: AxisIdx<Name, K>) -> Self {
+        let () = AxisInBounds::<K, D>::OK;
+        self.index[K] = index.index;
+        self
+    }
+}
+
+macro_rules! impl_from_axes {
+    ($d:literal; $($k:literal => $axis:ident),*) => {
+        impl<Name> From<($(AxisIdx<Name, $k>,)*)> for MultiIdx<Name, $d> {
+            fn from(($($axis,)*): ($(AxisIdx<Name, $k>,)*)) -> Self {
+                MultiIdx {
+                    index: [$($axis.index),*],
+                    _phantom: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+impl_from_axes!(1; 0 => a);
+impl_from_axes!(2; 0 => a, 1 => b);
+impl_from_axes!(3; 0 => a, 1 => b, 2 => c);
+impl_from_axes!(4; 0 => a, 1 => b, 2 => c, 3 => d);
+
+/// Where the elements of a tensor are in its buffer.
+#[derive(Clone, Copy, Debug)]
+struct Layout<const D: usize> {
+    offset: usize,
+    shape: [usize; D],
+    strides: [usize; D],
+}
+
+impl<const D: usize> Layout<D> {
+    /// A row-major layout, or ``None`` if the number of elements overflows a ``usize``.
+    fn row_major(shape: [usize; D]) -> Option<(Self, usize)> {
+        let mut strides = [0; D];
+        let mut len = 1usize;
+        for axis in (0..D).rev() {
+            strides[axis] = len;
+            len = len.checked_mul(shape[axis])?;
+        }
+        Some((Layout {
+            offset: 0,
+            shape,
+            strides,
+        }, len))
+    }
+
+    fn check_axis<Name, const K: usize>(&self, index: usize) -> Option<AxisIdx<Name, K>> {
+        let () = AxisInBounds::<K, D>::OK;
+        if index < self.shape[K] {
+            Some(AxisIdx {
+                index,
+                _phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn axis<Name, const K: usize>(&self) -> impl DoubleEndedIterator<Item = AxisIdx<Name, K>> + ExactSizeIterator {
+        let () = AxisInBounds::<K, D>::OK;
+        (0..self.shape[K]).map(|index| AxisIdx {
+            index,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn check_index<Name>(&self, index: [usize; D]) -> Option<MultiIdx<Name, D>> {
+        if index.iter().zip(&self.shape).all(|(i, len)| i < len) {
+            Some(MultiIdx {
+                index,
+                _phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The position of an element in the buffer. Only valid for checked indices.
+    #[inline(always)]
+    fn position(&self, index: &[usize; D]) -> usize {
+        index.iter().zip(&self.strides).fold(self.offset, |pos, (i, stride)| pos + i * stride)
+    }
+
+    fn slice_axis<const K: usize>(&self, range: Range<usize>) -> Option<Self> {
+        let () = AxisInBounds::<K, D>::OK;
+        if range.start > range.end || range.end > self.shape[K] {
+            return None;
+        }
+        let mut layout = *self;
+        if range.start < range.end {
+            layout.offset += range.start * self.strides[K];
+        }
+        layout.shape[K] = range.len();
+        Some(layout)
+    }
+
+    fn permuted(&self, axes: [usize; D]) -> Option<Self> {
+        let mut seen = [false; D];
+        for &axis in &axes {
+            if axis >= D || seen[axis] {
+                return None;
+            }
+            seen[axis] = true;
+        }
+        Some(Layout {
+            offset: self.offset,
+            shape: axes.map(|axis| self.shape[axis]),
+            strides: axes.map(|axis| self.strides[axis]),
+        })
+    }
+
+    fn reversed(&self) -> [usize; D] {
+        let mut axes = [0; D];
+        for (i, axis) in axes.iter_mut().enumerate() {
+            *axis = D - 1 - i;
+        }
+        axes
+    }
+}
+
+/// A ``D`` dimensional array stored in row-major order. The shape is checked once when the tensor
+/// is created, after which each axis can be indexed with an ``AxisIdx`` and every element with a
+/// ``MultiIdx`` without bounds checking. The shape of a ``FixedTensor`` never changes.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v: Vec<_> = (0..24).collect();
+/// let v = name!(v);
+/// let mut t = FixedTensor::fix(v, [2, 3, 4]).unwrap();
+///
+/// let i = t.check_axis::<0>(1).unwrap();
+/// let j = t.check_axis::<1>(2).unwrap();
+/// let k = t.check_axis::<2>(3).unwrap();
+///
+/// let index = MultiIdx::from((i, j, k));
+/// assert_eq!(t[index], 23);
+/// t[index] = 0;
+/// assert_eq!(t.axis::<2>().map(|k| t[index.with_axis(k)]).collect::<Vec<_>>(), vec![20, 21, 22, 0]);
+/// assert!(t.check_index([2, 0, 0]).is_none());
+/// ```
+///
+/// ```compile_fail
+/// # use fixed_vec::*;
+/// let v = vec![0; 6];
+/// let v = name!(v);
+/// let t = FixedTensor::fix(v, [2, 3]).unwrap();
+///
+/// t.check_axis::<2>(0);
+/// ```
+#[derive(Derivative)]
+#[derivative(Debug(bound="A: std::fmt::Debug"))]
+pub struct FixedTensor<A, Name, const D: usize> {
+    inner: Vec<A>,
+    layout: Layout<D>,
+    _phantom: PhantomData<Name>,
+}
+
+impl<A, Name, const D: usize> FixedTensor<A, Name, D> {
+    /// Create a ``FixedTensor`` with the given shape from a named ``Vec`` in row-major order. If
+    /// the length of the ``Vec`` isn't the product of the shape, it is given back unchanged.
+    pub fn fix(val: Named<Vec<A>, Name>, shape: [usize; D]) -> Result<Self, Vec<A>> {
+        let inner = val.unname();
+        match Layout::row_major(shape) {
+            Some((layout, len)) if len == inner.len() => Ok(FixedTensor {
+                inner,
+                layout,
+                _phantom: PhantomData,
+            }),
+            _ => Err(inner),
+        }
+    }
+
+    /// Unwrap the elements back into a ``Vec`` in row-major order. Since this takes ownership of
+    /// the ``FixedTensor``, it indirectly invalidates all indices with the same ``Name``.
+    pub fn unfix(self) -> Vec<A> {
+        self.inner
+    }
+
+    /// The length of each axis.
+    pub fn shape(&self) -> [usize; D] {
+        self.layout.shape
+    }
+
+    /// Perform a bounds check on a position along axis ``K``. Fails to compile if ``K`` isn't less
+    /// than ``D``.
+    pub fn check_axis<const K: usize>(&self, index: usize) -> Option<AxisIdx<Name, K>> {
+        self.layout.check_axis(index)
+    }
+
+    /// Returns an iterator over every position along axis ``K``.
+    pub fn axis<const K: usize>(&self) -> impl DoubleEndedIterator<Item = AxisIdx<Name, K>> + ExactSizeIterator {
+        self.layout.axis()
+    }
+
+    /// Perform a bounds check on every axis at once.
+    pub fn check_index(&self, index: [usize; D]) -> Option<MultiIdx<Name, D>> {
+        self.layout.check_index(index)
+    }
+
+    /// Get an element without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: MultiIdx<Name, D>) -> &A {
+        unsafe {
+            self.inner.get_unchecked(self.layout.position(&index.index))
+        }
+    }
+
+    /// Get a mutable reference to an element without bounds checking.
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: MultiIdx<Name, D>) -> &mut A {
+        unsafe {
+            self.inner.get_unchecked_mut(self.layout.position(&index.index))
+        }
+    }
+
+    /// Create a view of the whole tensor, which shares its name.
+    pub fn view(&self) -> FixedTensorView<'_, A, Name, D> {
+        FixedTensorView {
+            inner: &self.inner,
+            layout: self.layout,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create a view of the elements in ``range`` along axis ``K`` and pass it to ``f``. See
+    /// ``FixedTensorView::slice_axis``.
+    pub fn slice_axis<'b, const K: usize, R, F>(&'b self, range: Range<usize>, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedTensorView<'b, A, Brand<'id>, D>) -> R,
+    {
+        self.view().slice_axis::<K, R, F>(range, f)
+    }
+
+    /// Create a view with the axes reordered and pass it to ``f``. See
+    /// ``FixedTensorView::permuted``.
+    pub fn permuted<'b, R, F>(&'b self, axes: [usize; D], f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedTensorView<'b, A, Brand<'id>, D>) -> R,
+    {
+        self.view().permuted(axes, f)
+    }
+
+    /// Create a view with the axes in reverse order and pass it to ``f``. See
+    /// ``FixedTensorView::transposed``.
+    pub fn transposed<'b, R, F>(&'b self, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedTensorView<'b, A, Brand<'id>, D>) -> R,
+    {
+        self.view().transposed(f)
+    }
+}
+
+impl<A, Name, const D: usize> std::ops::Index<MultiIdx<Name, D>> for FixedTensor<A, Name, D> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: MultiIdx<Name, D>) -> &A {
+        self.get(index)
+    }
+}
+
+impl<A, Name, const D: usize> std::ops::IndexMut<MultiIdx<Name, D>> for FixedTensor<A, Name, D> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: MultiIdx<Name, D>) -> &mut A {
+        self.get_mut(index)
+    }
+}
+
+/// A view into a ``FixedTensor`` with its own shape and strides. Views created by slicing or
+/// permuting are passed to a closure and named with a fresh ``Brand``, so that their indices can't
+/// be confused with the indices of the tensor they came from, or of any other view.
+///
+/// # Example
+///
+/// ```
+/// # use fixed_vec::*;
+/// let v: Vec<_> = (0..6).collect();
+/// let v = name!(v);
+/// let t = FixedTensor::fix(v, [2, 3]).unwrap();
+///
+/// t.transposed(|tt| {
+///     assert_eq!(tt.shape(), [3, 2]);
+///     assert_eq!(tt[tt.check_index([2, 1]).unwrap()], 5);
+///
+///     tt.slice_axis::<0, _, _>(1..3, |cols| {
+///         let index = cols.check_index([0, 1]).unwrap();
+///         assert_eq!(cols[index], 4);
+///         assert!(cols.check_index([2, 0]).is_none());
+///     }).unwrap();
+/// });
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound=""), Copy(bound=""), Debug(bound="A: std::fmt::Debug"))]
+pub struct FixedTensorView<'a, A, Name, const D: usize> {
+    inner: &'a [A],
+    layout: Layout<D>,
+    _phantom: PhantomData<Name>,
+}
+
+impl<'a, A, Name, const D: usize> FixedTensorView<'a, A, Name, D> {
+    /// The length of each axis.
+    pub fn shape(&self) -> [usize; D] {
+        self.layout.shape
+    }
+
+    /// Perform a bounds check on a position along axis ``K``. Fails to compile if ``K`` isn't less
+    /// than ``D``.
+    pub fn check_axis<const K: usize>(&self, index: usize) -> Option<AxisIdx<Name, K>> {
+        self.layout.check_axis(index)
+    }
+
+    /// Returns an iterator over every position along axis ``K``.
+    pub fn axis<const K: usize>(&self) -> impl DoubleEndedIterator<Item = AxisIdx<Name, K>> + ExactSizeIterator {
+        self.layout.axis()
+    }
+
+    /// Perform a bounds check on every axis at once.
+    pub fn check_index(&self, index: [usize; D]) -> Option<MultiIdx<Name, D>> {
+        self.layout.check_index(index)
+    }
+
+    /// Get an element without bounds checking.
+    #[inline(always)]
+    pub fn get(&self, index: MultiIdx<Name, D>) -> &'a A {
+        unsafe {
+            self.inner.get_unchecked(self.layout.position(&index.index))
+        }
+    }
+
+    /// Create a view of the elements in ``range`` along axis ``K``, keeping every other axis, and
+    /// pass it to ``f``. The view is named with a fresh ``Brand``. Returns ``None`` without
+    /// calling ``f`` if the range is out of bounds.
+    pub fn slice_axis<const K: usize, R, F>(&self, range: Range<usize>, f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedTensorView<'a, A, Brand<'id>, D>) -> R,
+    {
+        Some(f(FixedTensorView {
+            inner: self.inner,
+            layout: self.layout.slice_axis::<K>(range)?,
+            _phantom: PhantomData,
+        }))
+    }
+
+    /// Create a view whose axis ``i`` is axis ``axes[i]`` of this view, and pass it to ``f``. The
+    /// view is named with a fresh ``Brand``. Returns ``None`` without calling ``f`` if ``axes``
+    /// isn't a permutation of ``0..D``.
+    pub fn permuted<R, F>(&self, axes: [usize; D], f: F) -> Option<R>
+    where
+        F: for<'id> FnOnce(FixedTensorView<'a, A, Brand<'id>, D>) -> R,
+    {
+        Some(f(FixedTensorView {
+            inner: self.inner,
+            layout: self.layout.permuted(axes)?,
+            _phantom: PhantomData,
+        }))
+    }
+
+    /// Create a view with the axes in reverse order, and pass it to ``f``. For a matrix, this is
+    /// the transpose. The view is named with a fresh ``Brand``.
+    pub fn transposed<R, F>(&self, f: F) -> R
+    where
+        F: for<'id> FnOnce(FixedTensorView<'a, A, Brand<'id>, D>) -> R,
+    {
+        match self.permuted(self.layout.reversed(), f) {
+            Some(result) => result,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<'a, A, Name, const D: usize> std::ops::Index<MultiIdx<Name, D>> for FixedTensorView<'a, A, Name, D> {
+    type Output = A;
+
+    #[inline(always)]
+    fn index(&self, index: MultiIdx<Name, D>) -> &A {
+        self.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use type_name_value::name;
+
+    #[test]
+    fn shapes() {
+        let v = name!(vec![0; 6]);
+        assert!(FixedTensor::fix(v, [4, 2]).is_err());
+        let v = name!(vec![0; 6]);
+        assert!(FixedTensor::fix(v, [usize::MAX, 2, 0]).is_err());
+        let v = name!(Vec::<i32>::new());
+        let t = FixedTensor::fix(v, [3, 0]).unwrap();
+        assert!(t.check_index([0, 0]).is_none());
+        assert_eq!(t.axis::<0>().len(), 3);
+
+        let v = name!(vec![7]);
+        let t = FixedTensor::fix(v, []).unwrap();
+        assert_eq!(t[t.check_index([]).unwrap()], 7);
+    }
+
+    #[test]
+    fn views() {
+        let v: Vec<_> = (0..24).collect();
+        let v = name!(v);
+        let t = FixedTensor::fix(v, [2, 3, 4]).unwrap();
+
+        assert!(t.permuted([0, 0, 1], |_| ()).is_none());
+        t.permuted([2, 0, 1], |p| {
+            assert_eq!(p.shape(), [4, 2, 3]);
+            assert_eq!(p[p.check_index([3, 1, 2]).unwrap()], 23);
+
+            p.slice_axis::<1, _, _>(1..2, |s| {
+                assert_eq!(s.shape(), [4, 1, 3]);
+                let index = s.check_index([1, 0, 0]).unwrap();
+                assert_eq!(s[index], 13);
+                assert_eq!(s.axis::<2>().map(|k| s[index.with_axis(k)]).collect::<Vec<_>>(), vec![13, 17, 21]);
+
+                assert!(s.slice_axis::<0, _, _>(2..5, |_| ()).is_none());
+                let empty = s.slice_axis::<0, _, _>(4..4, |empty| empty.check_index([0, 0, 0]).is_none());
+                assert_eq!(empty, Some(true));
+            }).unwrap();
+        }).unwrap();
+    }
+}